@@ -0,0 +1,295 @@
+//! # Analyzer Operations module
+//!
+//! - Defines the text analyzer configuration of an index (stemming, stop words, ASCII folding, case handling)
+//! - Stores the analyzer configuration alongside the indexed files
+//! - Registers the configured analyzer with the Tantivy index, so that documents and queries are tokenized the same way
+//! - Derives the analyzers of the language-specific content fields from the configuration
+
+use crate::error::IndexingError;
+use crate::language_operations::{is_cjk_language, CjkBigramTokenizer, SUPPORTED_LANGUAGES};
+use log::{debug, trace};
+use serde::{Deserialize, Serialize};
+use tantivy::Index;
+use tantivy::tokenizer::{AsciiFoldingFilter, Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, StopWordFilter, TextAnalyzer, TextAnalyzerBuilder};
+use pyo3::prelude::*;
+
+/// Name under which the configured analyzer is registered with the index
+pub const ANALYZER_NAME: &str = "pdf_seekers";
+
+/// Name of the file storing the analyzer configuration in the index directory
+pub const ANALYZER_CONFIG_FILE: &str = "analyzer.json";
+
+/// Tokens longer than this (in bytes) are dropped, as done by Tantivy's default analyzer
+const MAX_TOKEN_LENGTH: usize = 40;
+
+#[pyclass]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+/// Defines how page contents and metadata are tokenized, both at indexing and at search time
+pub struct AnalyzerOptions {
+    #[pyo3(get)]
+    /// Language used for stemming and the built-in stop-word list (e.g. `english`, `german`, `french`);
+    /// `chinese` and `japanese` texts are segmented into character bigrams instead
+    pub language: Option<String>,
+
+    #[pyo3(get)]
+    /// Reduce words to their stem, so that `networks` matches `network` (requires `language`)
+    pub stemming: bool,
+
+    #[pyo3(get)]
+    /// Remove the built-in stop words of `language`, such as `the` or `and` (requires `language`)
+    pub stop_words: bool,
+
+    #[pyo3(get)]
+    /// Additional words to be removed from page contents and queries
+    pub custom_stop_words: Vec<String>,
+
+    #[pyo3(get)]
+    /// Fold accented characters to their ASCII equivalent, so that `résumé` matches `resume`
+    pub ascii_folding: bool,
+
+    #[pyo3(get)]
+    /// Lowercase all tokens for case-insensitive matching
+    pub lowercase: bool,
+
+    #[pyo3(get)]
+    /// Route every page through the tokenizer and stemmer of its detected language
+    /// (`language` is then only used for pages whose language can't be detected)
+    pub detect_language: bool,
+}
+
+impl Default for AnalyzerOptions {
+    /// Same behaviour as Tantivy's default analyzer (lowercasing only)
+    fn default() -> Self {
+        AnalyzerOptions {
+            language: None,
+            stemming: false,
+            stop_words: false,
+            custom_stop_words: Vec::new(),
+            ascii_folding: false,
+            lowercase: true,
+            detect_language: false,
+        }
+    }
+}
+
+/// Parses a language name (case-insensitive) into a Tantivy language
+///
+/// ## Input Parameters
+/// - `language` defines the language name, e.g. `english`
+///
+/// ## Returns
+/// - Tantivy language supported by the stemmer and stop-word filters
+pub fn parse_language(language: &str) -> Result<Language, IndexingError> {
+    let language: Language = match language.trim().to_lowercase().as_str() {
+        "arabic" => Language::Arabic,
+        "danish" => Language::Danish,
+        "dutch" => Language::Dutch,
+        "english" => Language::English,
+        "finnish" => Language::Finnish,
+        "french" => Language::French,
+        "german" => Language::German,
+        "greek" => Language::Greek,
+        "hungarian" => Language::Hungarian,
+        "italian" => Language::Italian,
+        "norwegian" => Language::Norwegian,
+        "portuguese" => Language::Portuguese,
+        "romanian" => Language::Romanian,
+        "russian" => Language::Russian,
+        "spanish" => Language::Spanish,
+        "swedish" => Language::Swedish,
+        "tamil" => Language::Tamil,
+        "turkish" => Language::Turkish,
+        _ => return Err(IndexingError::InvalidAnalyzerOptions(format!("unsupported language `{}`", language)))
+    };
+
+    Ok(language)
+}
+
+/// Gets the name under which the analyzer of a language-specific content field is registered
+///
+/// ## Input Parameters
+/// - `language` defines the language name, e.g. `german`
+///
+/// ## Returns
+/// - Name of the analyzer, e.g. `pdf_seekers_german`
+pub fn language_analyzer_name(language: &str) -> String {
+    format!("{}_{}", ANALYZER_NAME, language)
+}
+
+/// Derives the analyzer configuration of a language-specific content field
+///
+/// The configured case handling, ASCII folding and custom stop words are kept, while
+/// stemming and built-in stop words use the given language (if available for it).
+///
+/// ## Input Parameters
+/// - `analyzer_options` defines the analyzer configuration of the index
+/// - `language` defines the language name, e.g. `german`
+///
+/// ## Returns
+/// - Analyzer configuration for pages written in the given language
+pub fn language_analyzer_options(analyzer_options: &AnalyzerOptions, language: &str) -> AnalyzerOptions {
+    let stemmer_language: Option<Language> = if is_cjk_language(language) { None } else { parse_language(language).ok() };
+
+    AnalyzerOptions {
+        language: Some(language.to_string()),
+        stemming: analyzer_options.stemming && stemmer_language.is_some(),
+        stop_words: analyzer_options.stop_words && stemmer_language.and_then(StopWordFilter::new).is_some(),
+        custom_stop_words: analyzer_options.custom_stop_words.clone(),
+        ascii_folding: analyzer_options.ascii_folding,
+        lowercase: analyzer_options.lowercase,
+        detect_language: false,
+    }
+}
+
+/// Builds the Tantivy text analyzer for the given configuration
+///
+/// Filters are applied in the following order: lowercasing, stop-word removal,
+/// ASCII folding and finally stemming. Stop-word lists still see accented
+/// characters, while the stemmer sees the same folded form for `résumé` and `resume`.
+/// Chinese and Japanese texts are segmented into character bigrams, and have neither
+/// a stemmer nor built-in stop words.
+///
+/// ## Input Parameters
+/// - `analyzer_options` defines the analyzer configuration
+///
+/// ## Returns
+/// - Text analyzer to be registered with the index
+pub fn build_text_analyzer(analyzer_options: &AnalyzerOptions) -> Result<TextAnalyzer, IndexingError> {
+    let cjk: bool = analyzer_options.language.as_deref().is_some_and(is_cjk_language);
+    let language: Option<Language> = match &analyzer_options.language {
+        Some(_) if cjk => None,
+        Some(s) => Some(parse_language(s)?),
+        None => None
+    };
+
+    // Pages whose language can't be detected skip the language-specific filters
+    let fallback: bool = analyzer_options.detect_language && analyzer_options.language.is_none();
+
+    let builder: TextAnalyzerBuilder = if cjk {
+        TextAnalyzer::builder(CjkBigramTokenizer).dynamic()
+    } else {
+        TextAnalyzer::builder(SimpleTokenizer::default()).dynamic()
+    };
+    let mut builder: TextAnalyzerBuilder = builder.filter_dynamic(RemoveLongFilter::limit(MAX_TOKEN_LENGTH));
+
+    if analyzer_options.lowercase {
+        builder = builder.filter_dynamic(LowerCaser);
+    }
+
+    if analyzer_options.stop_words && !fallback {
+        let stop_word_filter: StopWordFilter = match language.and_then(StopWordFilter::new) {
+            Some(s) => s,
+            None => return Err(IndexingError::InvalidAnalyzerOptions(format!("no built-in stop words for language {:?}", &analyzer_options.language)))
+        };
+        builder = builder.filter_dynamic(stop_word_filter);
+    }
+
+    if !analyzer_options.custom_stop_words.is_empty() {
+        let custom_stop_words = analyzer_options.custom_stop_words
+            .iter()
+            .map(|s| if analyzer_options.lowercase { s.to_lowercase() } else { s.clone() });
+        builder = builder.filter_dynamic(StopWordFilter::remove(custom_stop_words));
+    }
+
+    if analyzer_options.ascii_folding {
+        builder = builder.filter_dynamic(AsciiFoldingFilter);
+    }
+
+    if analyzer_options.stemming && !fallback {
+        let language: Language = match language {
+            Some(s) => s,
+            None if cjk => return Err(IndexingError::InvalidAnalyzerOptions(format!("no stemmer for language {:?}", &analyzer_options.language))),
+            None => return Err(IndexingError::InvalidAnalyzerOptions(String::from("stemming requires a language or language detection")))
+        };
+        builder = builder.filter_dynamic(Stemmer::new(language));
+    }
+
+    Ok(builder.build())
+}
+
+/// Stores the analyzer configuration in the index directory
+///
+/// ## Input Parameters
+/// - `index_path` defines the input path for storing the indexed files
+/// - `analyzer_options` defines the analyzer configuration to be stored
+///
+/// ## Returns
+/// - None
+pub fn save_analyzer_options(index_path: &str, analyzer_options: &AnalyzerOptions) -> Result<(), IndexingError> {
+    let config_file: String = format!("{}/{}", index_path, ANALYZER_CONFIG_FILE);
+
+    let config: String = match serde_json::to_string_pretty(analyzer_options) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::AnalyzerConfigParseError(config_file, e))
+    };
+
+    match std::fs::write(&config_file, config) {
+        Ok(_) => {
+            debug!(target:"other_logging", "Analyzer configuration stored at `{}` -> {:?}", &config_file, analyzer_options);
+            Ok(())
+        },
+        Err(e) => Err(IndexingError::AnalyzerConfigWriteError(config_file, e))
+    }
+}
+
+/// Loads the analyzer configuration stored in the index directory
+///
+/// Indexes created without a stored configuration use the default analyzer.
+///
+/// ## Input Parameters
+/// - `index_path` defines the input path for storing the indexed files
+///
+/// ## Returns
+/// - Analyzer configuration the index was created with
+pub fn load_analyzer_options(index_path: &str) -> Result<AnalyzerOptions, IndexingError> {
+    let config_file: String = format!("{}/{}", index_path, ANALYZER_CONFIG_FILE);
+
+    let config: String = match std::fs::read_to_string(&config_file) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            trace!(target:"other_logging", "No analyzer configuration found at `{}`. Using default analyzer.", &config_file);
+            return Ok(AnalyzerOptions::default());
+        },
+        Err(e) => return Err(IndexingError::AnalyzerConfigReadError(config_file, e))
+    };
+
+    match serde_json::from_str(&config) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(IndexingError::AnalyzerConfigParseError(config_file, e))
+    }
+}
+
+/// Registers the analyzer with the Tantivy index, to be used by its text fields
+///
+/// With language detection enabled, the analyzers of all language-specific content fields are registered as well.
+///
+/// ## Input Parameters
+/// - `index` contains the Tantivy index to be configured
+/// - `analyzer_options` defines the analyzer configuration
+///
+/// ## Returns
+/// - None
+pub fn register_analyzer(index: &Index, analyzer_options: &AnalyzerOptions) -> Result<(), IndexingError> {
+    let text_analyzer: TextAnalyzer = build_text_analyzer(analyzer_options)?;
+    index.tokenizers().register(ANALYZER_NAME, text_analyzer);
+
+    if analyzer_options.detect_language {
+        for language in SUPPORTED_LANGUAGES {
+            let text_analyzer: TextAnalyzer = build_text_analyzer(&language_analyzer_options(analyzer_options, language))?;
+            index.tokenizers().register(&language_analyzer_name(language), text_analyzer);
+        }
+    }
+
+    Ok(())
+}
+
+#[pymethods]
+impl AnalyzerOptions {
+    #[new]
+    #[pyo3(signature = (language=None, stemming=false, stop_words=false, custom_stop_words=Vec::new(), ascii_folding=false, lowercase=true, detect_language=false))]
+    /// Creates the text analyzer configuration
+    fn py_new(language: Option<String>, stemming: bool, stop_words: bool, custom_stop_words: Vec<String>, ascii_folding: bool, lowercase: bool, detect_language: bool) -> Self {
+        AnalyzerOptions { language, stemming, stop_words, custom_stop_words, ascii_folding, lowercase, detect_language }
+    }
+}
@@ -3,6 +3,8 @@
 //! - Defines the error types for file I/O operations
 //! - Defines the error types for index operations
 //! - Defines the error types for search operations
+//! - Defines the error types for logging setup
+//! - Defines the error type returned by the library entry points, wrapping all of the above
 
 use std::fmt::Display;
 
@@ -26,6 +28,16 @@ pub enum FileOperationsError {
     FileWriteError(String, std::io::Error),
     /// Unable to read file
     FileReadError(String, std::io::Error),
+    /// Unable to read file metadata (size, modification time)
+    FileMetadataReadError(String, std::io::Error),
+    /// Invalid include/exclude glob pattern
+    GlobPatternError(String, globset::Error),
+    /// Unable to parse `.seekerignore` file
+    IgnoreFileParseError(String, ignore::Error),
+    /// Input path doesn't exist
+    PathNotFoundError(String),
+    /// Input file doesn't start with a PDF header
+    NotPDFFileError(String),
 }
 
 impl Display for FileOperationsError {
@@ -47,6 +59,37 @@ impl Display for FileOperationsError {
                 write!(f, "[FO0007_FileWriteError] {}: {}", log_file, err),
             FileOperationsError::FileReadError(log_file, err) => 
                 write!(f, "[FO0008_FileReadError] {}: {}", log_file, err),
+            FileOperationsError::FileMetadataReadError(file_path, err) => 
+                write!(f, "[FO0009_FileMetadataReadError] {}: {}", file_path, err),
+            FileOperationsError::GlobPatternError(pattern, err) => 
+                write!(f, "[FO0010_GlobPatternError] {}: {}", pattern, err),
+            FileOperationsError::IgnoreFileParseError(file_path, err) => 
+                write!(f, "[FO0011_IgnoreFileParseError] {}: {}", file_path, err),
+            FileOperationsError::PathNotFoundError(path) => 
+                write!(f, "[FO0012_PathNotFoundError] {}: No such file or directory", path),
+            FileOperationsError::NotPDFFileError(file_path) => 
+                write!(f, "[FO0013_NotPDFFileError] {}: File is not a PDF document (missing `%PDF-` header)", file_path),
+        }
+    }
+}
+
+impl FileOperationsError {
+    /// Gets the code of the error (e.g. `FO0001_PDFFileReadError`), as shown at the start of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            FileOperationsError::PDFFileReadError(..) => "FO0001_PDFFileReadError",
+            FileOperationsError::PDFFileTextExtractionError(..) => "FO0002_PDFFileTextExtractionError",
+            FileOperationsError::DirectoryReadError(..) => "FO0003_DirectoryReadError",
+            FileOperationsError::CurrentWorkingDirectoryReadError(..) => "FO0004_CurrentWorkingDirectoryReadError",
+            FileOperationsError::DirectoryCreateError(..) => "FO0005_DirectoryCreateError",
+            FileOperationsError::FileOpenError(..) => "FO0006_FileOpenError",
+            FileOperationsError::FileWriteError(..) => "FO0007_FileWriteError",
+            FileOperationsError::FileReadError(..) => "FO0008_FileReadError",
+            FileOperationsError::FileMetadataReadError(..) => "FO0009_FileMetadataReadError",
+            FileOperationsError::GlobPatternError(..) => "FO0010_GlobPatternError",
+            FileOperationsError::IgnoreFileParseError(..) => "FO0011_IgnoreFileParseError",
+            FileOperationsError::PathNotFoundError(..) => "FO0012_PathNotFoundError",
+            FileOperationsError::NotPDFFileError(..) => "FO0013_NotPDFFileError",
         }
     }
 }
@@ -70,6 +113,28 @@ pub enum IndexingError {
     IndexDocumentAddError(tantivy::error::TantivyError),
     /// Unable to commit new index file to index directory
     IndexDocumentCommitError(tantivy::error::TantivyError),
+    /// Unable to create Tantivy index reader
+    IndexReaderCreateError(tantivy::error::TantivyError),
+    /// Unable to look up indexed documents for a PDF file
+    IndexDocumentFetchError(String, tantivy::error::TantivyError),
+    /// Unable to complete merging of index segments
+    IndexSegmentMergeError(tantivy::error::TantivyError),
+    /// Unable to store analyzer configuration in index directory
+    AnalyzerConfigWriteError(String, std::io::Error),
+    /// Unable to read analyzer configuration from index directory
+    AnalyzerConfigReadError(String, std::io::Error),
+    /// Unable to serialize or parse analyzer configuration
+    AnalyzerConfigParseError(String, serde_json::Error),
+    /// Analyzer configuration cannot be used to build a text analyzer
+    InvalidAnalyzerOptions(String),
+    /// Unable to record a PDF file which failed to be indexed
+    TrackFileWriteError(FileOperationsError),
+    /// Index was created with another schema version and has to be rebuilt
+    IndexRebuildRequired(String, u32, u32),
+    /// Unable to store schema version in index directory
+    SchemaVersionWriteError(String, std::io::Error),
+    /// Unable to read schema version from index directory
+    SchemaVersionReadError(String, std::io::Error),
 }
 
 impl Display for IndexingError {
@@ -91,6 +156,55 @@ impl Display for IndexingError {
                 write!(f, "[IE0007_IndexDocumentAddError] {}", err),
             IndexingError::IndexDocumentCommitError(err) => 
                 write!(f, "[IE0008_IndexDocumentCommitError] {}", err),
+            IndexingError::IndexReaderCreateError(err) => 
+                write!(f, "[IE0009_IndexReaderCreateError] {}", err),
+            IndexingError::IndexDocumentFetchError(file_name, err) => 
+                write!(f, "[IE0010_IndexDocumentFetchError] {}: {}", file_name, err),
+            IndexingError::IndexSegmentMergeError(err) => 
+                write!(f, "[IE0011_IndexSegmentMergeError] {}", err),
+            IndexingError::AnalyzerConfigWriteError(file_path, err) => 
+                write!(f, "[IE0012_AnalyzerConfigWriteError] {}: {}", file_path, err),
+            IndexingError::AnalyzerConfigReadError(file_path, err) => 
+                write!(f, "[IE0013_AnalyzerConfigReadError] {}: {}", file_path, err),
+            IndexingError::AnalyzerConfigParseError(file_path, err) => 
+                write!(f, "[IE0014_AnalyzerConfigParseError] {}: {}", file_path, err),
+            IndexingError::InvalidAnalyzerOptions(reason) => 
+                write!(f, "[IE0015_InvalidAnalyzerOptions] {}", reason),
+            IndexingError::TrackFileWriteError(err) => 
+                write!(f, "[IE0016_TrackFileWriteError] {}", err),
+            IndexingError::IndexRebuildRequired(dir_path, found, expected) => 
+                write!(f, "[IE0017_IndexRebuildRequired] {}: Index has schema version {} but version {} is required. Delete the index directory (or use a new cache path) and index the PDF files again", dir_path, found, expected),
+            IndexingError::SchemaVersionWriteError(file_path, err) => 
+                write!(f, "[IE0018_SchemaVersionWriteError] {}: {}", file_path, err),
+            IndexingError::SchemaVersionReadError(file_path, err) => 
+                write!(f, "[IE0019_SchemaVersionReadError] {}: {}", file_path, err),
+        }
+    }
+}
+
+impl IndexingError {
+    /// Gets the code of the error (e.g. `IE0001_IndexDirectoryOpenError`), as shown at the start of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            IndexingError::IndexDirectoryOpenError(..) => "IE0001_IndexDirectoryOpenError",
+            IndexingError::IndexDirectoryReadError(..) => "IE0002_IndexDirectoryReadError",
+            IndexingError::IndexDirectoryCreateError(..) => "IE0003_IndexDirectoryCreateError",
+            IndexingError::IndexCreateError(..) => "IE0004_IndexCreateError",
+            IndexingError::IndexWriterCreateError(..) => "IE0005_IndexWriterCreateError",
+            IndexingError::IndexFieldNotFound(..) => "IE0006_IndexFieldNotFound",
+            IndexingError::IndexDocumentAddError(..) => "IE0007_IndexDocumentAddError",
+            IndexingError::IndexDocumentCommitError(..) => "IE0008_IndexDocumentCommitError",
+            IndexingError::IndexReaderCreateError(..) => "IE0009_IndexReaderCreateError",
+            IndexingError::IndexDocumentFetchError(..) => "IE0010_IndexDocumentFetchError",
+            IndexingError::IndexSegmentMergeError(..) => "IE0011_IndexSegmentMergeError",
+            IndexingError::AnalyzerConfigWriteError(..) => "IE0012_AnalyzerConfigWriteError",
+            IndexingError::AnalyzerConfigReadError(..) => "IE0013_AnalyzerConfigReadError",
+            IndexingError::AnalyzerConfigParseError(..) => "IE0014_AnalyzerConfigParseError",
+            IndexingError::InvalidAnalyzerOptions(..) => "IE0015_InvalidAnalyzerOptions",
+            IndexingError::TrackFileWriteError(..) => "IE0016_TrackFileWriteError",
+            IndexingError::IndexRebuildRequired(..) => "IE0017_IndexRebuildRequired",
+            IndexingError::SchemaVersionWriteError(..) => "IE0018_SchemaVersionWriteError",
+            IndexingError::SchemaVersionReadError(..) => "IE0019_SchemaVersionReadError",
         }
     }
 }
@@ -108,6 +222,12 @@ pub enum SearchingError {
     KeywordSearchError(tantivy::error::TantivyError),
     /// Unable to get matched PDF files from Tantivy index
     SearcherDocumentFetchError(tantivy::error::TantivyError),
+    /// Invalid search configuration (e.g. unsupported fuzzy distance)
+    InvalidSearchOptions(String),
+    /// Unable to build a query from a wildcard or regex search pattern
+    InvalidSearchPattern(String, tantivy::error::TantivyError),
+    /// Unable to create Tantivy snippet generator for highlighting matched pages
+    SnippetGeneratorError(tantivy::error::TantivyError),
 }
 
 impl Display for SearchingError {
@@ -123,10 +243,233 @@ impl Display for SearchingError {
                 write!(f, "[SE0004_KeywordSearchError] {}", err),
             SearchingError::SearcherDocumentFetchError(err) => 
                 write!(f, "[SE0005_SearcherDocumentFetchError] {}", err),
+            SearchingError::InvalidSearchOptions(reason) => 
+                write!(f, "[SE0006_InvalidSearchOptions] {}", reason),
+            SearchingError::InvalidSearchPattern(pattern, err) => 
+                write!(f, "[SE0007_InvalidSearchPattern] {} - {}", pattern, err),
+            SearchingError::SnippetGeneratorError(err) => 
+                write!(f, "[SE0008_SnippetGeneratorError] {}", err),
+        }
+    }
+}
+
+impl SearchingError {
+    /// Gets the code of the error (e.g. `SE0001_IndexReaderCreateError`), as shown at the start of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            SearchingError::IndexReaderCreateError(..) => "SE0001_IndexReaderCreateError",
+            SearchingError::IndexFieldNotFound(..) => "SE0002_IndexFieldNotFound",
+            SearchingError::QueryParserError(..) => "SE0003_QueryParserError",
+            SearchingError::KeywordSearchError(..) => "SE0004_KeywordSearchError",
+            SearchingError::SearcherDocumentFetchError(..) => "SE0005_SearcherDocumentFetchError",
+            SearchingError::InvalidSearchOptions(..) => "SE0006_InvalidSearchOptions",
+            SearchingError::InvalidSearchPattern(..) => "SE0007_InvalidSearchPattern",
+            SearchingError::SnippetGeneratorError(..) => "SE0008_SnippetGeneratorError",
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Defines the logging setup error types
+pub enum LoggingError {
+    /// Log verbosity level is not one of INFO, WARN, DEBUG, ERROR, TRACE, OFF
+    InvalidLogLevel(String),
+    /// Unable to create or open log file
+    LogFileCreateError(String, std::io::Error),
+    /// Unable to build Log4rs configuration
+    LogConfigError(log4rs::config::runtime::ConfigErrors),
+    /// Another global logger was already installed by the embedding application
+    LoggerAlreadySetError(log::SetLoggerError),
+    /// Unable to create or open JSON-lines log file
+    JsonLogFileCreateError(String, std::io::Error),
+    /// Another global tracing subscriber was already installed by the embedding application
+    SubscriberAlreadySetError(tracing::subscriber::SetGlobalDefaultError),
+}
+
+impl Display for LoggingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoggingError::InvalidLogLevel(log_level) => 
+                write!(f, "[LE0001_InvalidLogLevel] {}: Allowed values are INFO, WARN, DEBUG, ERROR, TRACE, OFF", log_level),
+            LoggingError::LogFileCreateError(log_file, err) => 
+                write!(f, "[LE0002_LogFileCreateError] {}: {}", log_file, err),
+            LoggingError::LogConfigError(err) => 
+                write!(f, "[LE0003_LogConfigError] {}", err),
+            LoggingError::LoggerAlreadySetError(err) => 
+                write!(f, "[LE0004_LoggerAlreadySetError] {}", err),
+            LoggingError::JsonLogFileCreateError(log_file, err) => 
+                write!(f, "[LE0005_JsonLogFileCreateError] {}: {}", log_file, err),
+            LoggingError::SubscriberAlreadySetError(err) => 
+                write!(f, "[LE0006_SubscriberAlreadySetError] {}", err),
+        }
+    }
+}
+
+impl LoggingError {
+    /// Gets the code of the error (e.g. `LE0001_InvalidLogLevel`), as shown at the start of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            LoggingError::InvalidLogLevel(..) => "LE0001_InvalidLogLevel",
+            LoggingError::LogFileCreateError(..) => "LE0002_LogFileCreateError",
+            LoggingError::LogConfigError(..) => "LE0003_LogConfigError",
+            LoggingError::LoggerAlreadySetError(..) => "LE0004_LoggerAlreadySetError",
+            LoggingError::JsonLogFileCreateError(..) => "LE0005_JsonLogFileCreateError",
+            LoggingError::SubscriberAlreadySetError(..) => "LE0006_SubscriberAlreadySetError",
+        }
+    }
+}
+
+#[derive(Debug)]
+/// Defines the error type returned by the library entry points
+/// 
+/// Every variant wraps the error of the failed operation and is displayed as that error,
+/// so `source()` continues with the underlying cause (e.g. the Tantivy or I/O error).
+pub enum PdfSeekerError {
+    /// File I/O operation failed
+    FileOperations(FileOperationsError),
+    /// Index operation failed
+    Indexing(IndexingError),
+    /// Search operation failed
+    Searching(SearchingError),
+    /// Logging setup failed
+    Logging(LoggingError),
+}
+
+impl Display for PdfSeekerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PdfSeekerError::FileOperations(err) => write!(f, "{}", err),
+            PdfSeekerError::Indexing(err) => write!(f, "{}", err),
+            PdfSeekerError::Searching(err) => write!(f, "{}", err),
+            PdfSeekerError::Logging(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl PdfSeekerError {
+    /// Gets the code of the wrapped error (e.g. `FO0012_PathNotFoundError`), as shown at the start of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            PdfSeekerError::FileOperations(err) => err.code(),
+            PdfSeekerError::Indexing(err) => err.code(),
+            PdfSeekerError::Searching(err) => err.code(),
+            PdfSeekerError::Logging(err) => err.code(),
+        }
+    }
+}
+
+impl From<FileOperationsError> for PdfSeekerError {
+    fn from(err: FileOperationsError) -> Self {
+        PdfSeekerError::FileOperations(err)
+    }
+}
+
+impl From<IndexingError> for PdfSeekerError {
+    fn from(err: IndexingError) -> Self {
+        PdfSeekerError::Indexing(err)
+    }
+}
+
+impl From<SearchingError> for PdfSeekerError {
+    fn from(err: SearchingError) -> Self {
+        PdfSeekerError::Searching(err)
+    }
+}
+
+impl From<LoggingError> for PdfSeekerError {
+    fn from(err: LoggingError) -> Self {
+        PdfSeekerError::Logging(err)
+    }
+}
+
+impl std::error::Error for FileOperationsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FileOperationsError::PDFFileReadError(_, err) => Some(err),
+            FileOperationsError::PDFFileTextExtractionError(_, _, err) => Some(err),
+            FileOperationsError::DirectoryReadError(_, err) => Some(err),
+            FileOperationsError::CurrentWorkingDirectoryReadError(err) => Some(err),
+            FileOperationsError::DirectoryCreateError(_, err) => Some(err),
+            FileOperationsError::FileOpenError(_, err) => Some(err),
+            FileOperationsError::FileWriteError(_, err) => Some(err),
+            FileOperationsError::FileReadError(_, err) => Some(err),
+            FileOperationsError::FileMetadataReadError(_, err) => Some(err),
+            FileOperationsError::GlobPatternError(_, err) => Some(err),
+            FileOperationsError::IgnoreFileParseError(_, err) => Some(err),
+            FileOperationsError::PathNotFoundError(_) => None,
+            FileOperationsError::NotPDFFileError(_) => None,
+        }
+    }
+}
+
+impl std::error::Error for IndexingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IndexingError::IndexDirectoryOpenError(_, err) => Some(err),
+            IndexingError::IndexDirectoryReadError(_, err) => Some(err),
+            IndexingError::IndexDirectoryCreateError(_, err) => Some(err),
+            IndexingError::IndexCreateError(_, err) => Some(err),
+            IndexingError::IndexWriterCreateError(err) => Some(err),
+            IndexingError::IndexFieldNotFound(_, err) => Some(err),
+            IndexingError::IndexDocumentAddError(err) => Some(err),
+            IndexingError::IndexDocumentCommitError(err) => Some(err),
+            IndexingError::IndexReaderCreateError(err) => Some(err),
+            IndexingError::IndexDocumentFetchError(_, err) => Some(err),
+            IndexingError::IndexSegmentMergeError(err) => Some(err),
+            IndexingError::AnalyzerConfigWriteError(_, err) => Some(err),
+            IndexingError::AnalyzerConfigReadError(_, err) => Some(err),
+            IndexingError::AnalyzerConfigParseError(_, err) => Some(err),
+            IndexingError::InvalidAnalyzerOptions(_) => None,
+            IndexingError::TrackFileWriteError(err) => Some(err),
+            IndexingError::IndexRebuildRequired(_, _, _) => None,
+            IndexingError::SchemaVersionWriteError(_, err) => Some(err),
+            IndexingError::SchemaVersionReadError(_, err) => Some(err),
         }
     }
 }
 
-impl std::error::Error for FileOperationsError {}
-impl std::error::Error for IndexingError {}
-impl std::error::Error for SearchingError {}
\ No newline at end of file
+impl std::error::Error for SearchingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SearchingError::IndexReaderCreateError(err) => Some(err),
+            SearchingError::IndexFieldNotFound(_, err) => Some(err),
+            SearchingError::QueryParserError(err) => Some(err),
+            SearchingError::KeywordSearchError(err) => Some(err),
+            SearchingError::SearcherDocumentFetchError(err) => Some(err),
+            SearchingError::InvalidSearchOptions(_) => None,
+            SearchingError::InvalidSearchPattern(_, err) => Some(err),
+            SearchingError::SnippetGeneratorError(err) => Some(err),
+        }
+    }
+}
+
+impl std::error::Error for LoggingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoggingError::InvalidLogLevel(_) => None,
+            LoggingError::LogFileCreateError(_, err) => Some(err),
+            LoggingError::LogConfigError(err) => Some(err),
+            LoggingError::LoggerAlreadySetError(err) => Some(err),
+            LoggingError::JsonLogFileCreateError(_, err) => Some(err),
+            LoggingError::SubscriberAlreadySetError(err) => Some(err),
+        }
+    }
+}
+
+impl std::error::Error for PdfSeekerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PdfSeekerError::FileOperations(err) => err.source(),
+            PdfSeekerError::Indexing(err) => err.source(),
+            PdfSeekerError::Searching(err) => err.source(),
+            PdfSeekerError::Logging(err) => err.source(),
+        }
+    }
+}
+
+impl From<PdfSeekerError> for pyo3::PyErr {
+    /// Raises failed operations as Python exceptions, carrying the error code and message
+    fn from(err: PdfSeekerError) -> pyo3::PyErr {
+        pyo3::exceptions::PyRuntimeError::new_err(err.to_string())
+    }
+}
@@ -1,29 +1,288 @@
 //! # File Operations module
 //! 
 //! - Defines the supporting functions for performing file I/O operations
-//! - Defines the supporting functions for getting all files in a directory
+//! - Defines the supporting functions for getting all files in a directory tree (honouring `.seekerignore` files)
+//! - Defines the supporting functions for reading PDF document-info and XMP metadata
+//! - Detects the language of every extracted PDF page (if enabled)
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc, FixedOffset};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use lopdf::Document as lopdoc;
-use log::trace;
+use lopdf::Object;
+use log::{trace, warn};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::io::Write;
 use std::io::BufRead;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
 use crate::error::FileOperationsError;
+use crate::language_operations::detect_language;
+use pyo3::prelude::*;
+
+/// Name of the files holding gitignore-style rules for skipping paths during directory traversal
+pub const IGNORE_FILE_NAME: &str = ".seekerignore";
+
+/// Magic bytes at the start of every PDF document
+const PDF_MAGIC_BYTES: &[u8] = b"%PDF-";
+
+/// Number of leading bytes searched for the PDF header
+const PDF_HEADER_SEARCH_LIMIT: usize = 1024;
+
+/// Page numbers, page texts, detected page languages and document-level metadata extracted from a PDF file
+pub type PdfPages = (Vec<u32>, Vec<String>, Vec<Option<String>>, DocumentInfo);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Defines the fingerprint used for detecting changes to an indexed PDF file
+pub struct FileFingerprint {
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Last modification time of the file, in nanoseconds since UNIX epoch
+    pub modified: u64,
+    /// SHA-256 hash of the file contents (hex encoded)
+    pub content_hash: String
+}
+
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+/// Defines how a directory tree is traversed when collecting PDF files
+pub struct TraversalOptions {
+    #[pyo3(get)]
+    /// Maximum number of subdirectory levels to descend into (0 only reads the directory itself, None is unlimited)
+    pub max_depth: Option<usize>,
+
+    #[pyo3(get)]
+    /// Follow symbolic links to files and directories
+    pub follow_symlinks: bool,
+
+    #[pyo3(get)]
+    /// Glob patterns of files to be included (all PDF files are included if empty)
+    pub include: Vec<String>,
+
+    #[pyo3(get)]
+    /// Glob patterns of files and directories to be excluded
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Defines the document-level metadata of a PDF file (Info dictionary and XMP metadata)
+pub struct DocumentInfo {
+    /// Title of the PDF document
+    pub title: Option<String>,
+    /// Author(s) of the PDF document
+    pub author: Option<String>,
+    /// Subject of the PDF document
+    pub subject: Option<String>,
+    /// Keywords associated with the PDF document
+    pub keywords: Option<String>,
+    /// Application which produced the PDF document
+    pub producer: Option<String>,
+    /// Creation date of the PDF document
+    pub creation_date: Option<DateTime<Utc>>,
+    /// Last modification date of the PDF document
+    pub mod_date: Option<DateTime<Utc>>,
+}
+
+/// Decodes a PDF text string (UTF-16BE with byte order mark, UTF-8 with byte order mark, or PDFDocEncoding)
+fn decode_pdf_text_string(bytes: &[u8]) -> String {
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        let utf16: Vec<u16> = bytes[2..]
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16_lossy(&utf16)
+    }
+    else if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8_lossy(&bytes[3..]).to_string()
+    }
+    else {
+        // PDFDocEncoding matches Latin-1 for all printable characters
+        bytes.iter().map(|&b| b as char).collect()
+    }
+}
+
+/// Parses a PDF date string of the form `D:YYYYMMDDHHmmSSOHH'mm'` (all parts after the year are optional)
+pub fn parse_pdf_date(date: &str) -> Option<DateTime<Utc>> {
+    let date: &str = date.trim();
+    let date: &str = date.strip_prefix("D:").unwrap_or(date);
+
+    let digits: String = date.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.len() < 4 {
+        return None;
+    }
+
+    let part = |start: usize, len: usize, default: u32| -> u32 {
+        digits.get(start..start + len).and_then(|s| s.parse::<u32>().ok()).unwrap_or(default)
+    };
+
+    let year: i32 = digits[0..4].parse::<i32>().ok()?;
+    let naive: NaiveDateTime = NaiveDate::from_ymd_opt(year, part(4, 2, 1), part(6, 2, 1))?
+        .and_hms_opt(part(8, 2, 0), part(10, 2, 0), part(12, 2, 0))?;
+
+    // Apply the timezone offset, if any (`Z`, `+HH'mm'` or `-HH'mm'`)
+    let offset: String = date[digits.len()..].replace('\'', "");
+    let offset_secs: i32 = match offset.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let hours: i32 = offset.get(1..3).and_then(|s| s.parse::<i32>().ok()).unwrap_or(0);
+            let minutes: i32 = offset.get(3..5).and_then(|s| s.parse::<i32>().ok()).unwrap_or(0);
+            let secs: i32 = hours * 3600 + minutes * 60;
+            if sign == '-' { -secs } else { secs }
+        },
+        _ => 0
+    };
+
+    FixedOffset::east_opt(offset_secs)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Parses an XMP (ISO 8601) date string, with or without time and timezone
+fn parse_xmp_date(date: &str) -> Option<DateTime<Utc>> {
+    let date: &str = date.trim();
+
+    if let Ok(s) = DateTime::parse_from_rfc3339(date) {
+        return Some(s.with_timezone(&Utc));
+    }
+    if let Ok(s) = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S") {
+        return Some(Utc.from_utc_datetime(&s));
+    }
+    if let Ok(s) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        return s.and_hms_opt(0, 0, 0).map(|d| Utc.from_utc_datetime(&d));
+    }
+
+    None
+}
+
+/// Reads an entry from the PDF Info dictionary as text (empty values are ignored)
+fn get_info_text(doc: &lopdoc, info: &lopdf::Dictionary, key: &[u8]) -> Option<String> {
+    let value: String = match info.get_deref(key, doc) {
+        Ok(Object::String(bytes, _)) => decode_pdf_text_string(bytes),
+        _ => return None
+    };
+
+    let value: &str = value.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+    if value.is_empty() { None } else { Some(value.to_string()) }
+}
+
+/// Reads the value of an XMP property, either written as element or as attribute
+/// 
+/// Values held in `rdf:Seq`, `rdf:Bag` or `rdf:Alt` containers are joined with `; `.
+fn get_xmp_value(xmp: &str, property: &str) -> Option<String> {
+    // Attribute form: <rdf:Description xmp:CreateDate="...">
+    let attribute: String = format!("{}=\"", property);
+    if let Some(start) = xmp.find(&attribute) {
+        let rest: &str = &xmp[start + attribute.len()..];
+        let value: &str = &rest[..rest.find('"')?];
+        return Some(unescape_xml(value)).filter(|s| !s.trim().is_empty());
+    }
+
+    // Element form: <dc:title><rdf:Alt><rdf:li xml:lang="x-default">...</rdf:li></rdf:Alt></dc:title>
+    let open_tag: String = format!("<{}", property);
+    let close_tag: String = format!("</{}>", property);
+    let start: usize = xmp.find(&open_tag)?;
+    let rest: &str = &xmp[start + open_tag.len()..];
+    let rest: &str = &rest[rest.find('>')? + 1..];
+    let inner: &str = &rest[..rest.find(&close_tag)?];
+
+    // Collect the text nodes between the tags
+    let mut values: Vec<String> = Vec::new();
+    let mut text: String = String::new();
+    let mut in_tag: bool = false;
+    for c in inner.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                if !text.trim().is_empty() {
+                    values.push(unescape_xml(text.trim()));
+                }
+                text.clear();
+            },
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    if !text.trim().is_empty() {
+        values.push(unescape_xml(text.trim()));
+    }
+
+    if values.is_empty() { None } else { Some(values.join("; ")) }
+}
+
+/// Replaces the predefined XML entities with their characters
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Reads the document-info dictionary and XMP metadata stream of a PDF document
+/// 
+/// Values from the Info dictionary take precedence; missing values are taken from XMP metadata.
+/// 
+/// ## Input Parameters
+/// - `doc` contains the loaded PDF document
+/// 
+/// ## Returns
+/// - `DocumentInfo` struct containing the document-level metadata
+pub fn read_document_info(doc: &lopdoc) -> DocumentInfo {
+    let mut info: DocumentInfo = DocumentInfo::default();
+
+    // Read the Info dictionary referenced from the trailer
+    if let Ok(dict) = doc.trailer.get_deref(b"Info", doc).and_then(Object::as_dict) {
+        info.title = get_info_text(doc, dict, b"Title");
+        info.author = get_info_text(doc, dict, b"Author");
+        info.subject = get_info_text(doc, dict, b"Subject");
+        info.keywords = get_info_text(doc, dict, b"Keywords");
+        info.producer = get_info_text(doc, dict, b"Producer");
+        info.creation_date = get_info_text(doc, dict, b"CreationDate").and_then(|s| parse_pdf_date(&s));
+        info.mod_date = get_info_text(doc, dict, b"ModDate").and_then(|s| parse_pdf_date(&s));
+    }
+
+    // Read the XMP metadata stream referenced from the document catalog
+    let xmp: Option<String> = doc.catalog().ok()
+        .and_then(|catalog| catalog.get_deref(b"Metadata", doc).ok())
+        .and_then(|object| object.as_stream().ok())
+        .map(|stream| stream.decompressed_content().unwrap_or_else(|_| stream.content.clone()))
+        .map(|content| String::from_utf8_lossy(&content).to_string());
+
+    if let Some(xmp) = xmp {
+        info.title = info.title.or_else(|| get_xmp_value(&xmp, "dc:title"));
+        info.author = info.author.or_else(|| get_xmp_value(&xmp, "dc:creator"));
+        info.subject = info.subject.or_else(|| get_xmp_value(&xmp, "dc:description"));
+        info.keywords = info.keywords.or_else(|| get_xmp_value(&xmp, "pdf:Keywords"));
+        info.producer = info.producer.or_else(|| get_xmp_value(&xmp, "pdf:Producer"));
+        info.creation_date = info.creation_date.or_else(|| get_xmp_value(&xmp, "xmp:CreateDate").and_then(|s| parse_xmp_date(&s)));
+        info.mod_date = info.mod_date.or_else(|| get_xmp_value(&xmp, "xmp:ModifyDate").and_then(|s| parse_xmp_date(&s)));
+    }
+
+    info
+}
 
 /// Read a PDF files to extract its contents
 /// 
 /// ## Input Parameters
 /// - `pdf_file` contains the PDF file to be read
+/// - `detect_languages` defines whether the language of every page is detected (left unset otherwise)
 /// - `track_fail_file` defines the file path for storing failed processing file names
 /// 
 /// ## Returns
-/// - Extract text from PDF file
-pub fn read_pdf(pdf_file: &String, track_fail_file: Option<&String>) -> Result<(Vec<u32>, Vec<String>), FileOperationsError> {
+/// - Extract text, detected language of every page and document-level metadata from PDF file
+#[allow(clippy::needless_borrows_for_generic_args, clippy::single_match)]
+pub fn read_pdf(pdf_file: &String, detect_languages: bool, track_fail_file: Option<&String>) -> Result<PdfPages, FileOperationsError> {
     // Read the PDF file
     let doc = match lopdoc::load(&pdf_file) {
         Ok(s) => s,
         Err(e) => {
             match track_fail_file {
-                Some(s) => write_to_file(s, pdf_file).unwrap(),
+                Some(s) => write_to_file(s, pdf_file)?,
                 None => {}
             }
             return Err(FileOperationsError::PDFFileReadError(pdf_file.to_string().clone(), e))
@@ -35,6 +294,7 @@ pub fn read_pdf(pdf_file: &String, track_fail_file: Option<&String>) -> Result<(
     let pages = doc.get_pages();
     let mut pdf_page_nums: Vec<u32> = Vec::new();
     let mut pdf_texts: Vec<String> = Vec::new();
+    let mut pdf_languages: Vec<Option<String>> = Vec::new();
 
     // Traverse through the PDF pages to extract text
     for (i, _) in pages.iter().enumerate() {
@@ -46,83 +306,694 @@ pub fn read_pdf(pdf_file: &String, track_fail_file: Option<&String>) -> Result<(
             Err(e) => return Err(FileOperationsError::PDFFileTextExtractionError(pdf_file.to_string().clone(), page_number, e))
         };
         
+        // Detect the language of the PDF page
+        let language: Option<String> = if detect_languages { detect_language(&text) } else { None };
+        if detect_languages {
+            trace!(target:"other_logging", "PDF document `{}` page {} language -> {:?}", &pdf_file, page_number, &language);
+        }
+
         pdf_page_nums.push(page_number);
         pdf_texts.push(text);
+        pdf_languages.push(language);
     }
 
-    Ok((pdf_page_nums, pdf_texts))
+    // Read the document-info and XMP metadata
+    let document_info: DocumentInfo = read_document_info(&doc);
+    trace!(target:"other_logging", "PDF document `{}` metadata -> {:?}", &pdf_file, &document_info);
+
+    Ok((pdf_page_nums, pdf_texts, pdf_languages, document_info))
+}
+
+/// Reads the size and last modification time of a file
+/// 
+/// ## Input Parameters
+/// - `file_path` defines the file to be analysed
+/// 
+/// ## Returns
+/// - Tuple containing file size (in bytes) and last modification time (in nanoseconds since UNIX epoch)
+pub fn get_file_stats(file_path: &str) -> Result<(u64, u64), FileOperationsError> {
+    let metadata = match std::fs::metadata(file_path) {
+        Ok(s) => s,
+        Err(e) => return Err(FileOperationsError::FileMetadataReadError(file_path.to_string(), e))
+    };
+
+    let modified: u64 = match metadata.modified() {
+        Ok(s) => s.duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or_default(),
+        Err(e) => return Err(FileOperationsError::FileMetadataReadError(file_path.to_string(), e))
+    };
+
+    Ok((metadata.len(), modified))
+}
+
+/// Computes the SHA-256 hash of the file contents
+/// 
+/// ## Input Parameters
+/// - `file_path` defines the file to be hashed
+/// 
+/// ## Returns
+/// - Hex encoded SHA-256 hash of the file contents
+pub fn compute_content_hash(file_path: &str) -> Result<String, FileOperationsError> {
+    let mut file = match std::fs::File::open(file_path) {
+        Ok(s) => s,
+        Err(e) => return Err(FileOperationsError::FileOpenError(file_path.to_string(), e))
+    };
+
+    let mut hasher = Sha256::new();
+    match std::io::copy(&mut file, &mut hasher) {
+        Ok(_) => {},
+        Err(e) => return Err(FileOperationsError::FileReadError(file_path.to_string(), e))
+    };
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Computes the fingerprint (size, modification time and content hash) of a file
+/// 
+/// ## Input Parameters
+/// - `file_path` defines the file to be fingerprinted
+/// 
+/// ## Returns
+/// - `FileFingerprint` struct for the given file
+pub fn get_file_fingerprint(file_path: &str) -> Result<FileFingerprint, FileOperationsError> {
+    let (size, modified) = get_file_stats(file_path)?;
+    let content_hash: String = compute_content_hash(file_path)?;
+
+    Ok(FileFingerprint { size, modified, content_hash })
 }
 
 /// Checks if given input is a file or directory
 /// 
+/// Symbolic links are followed, so a link to a directory is treated as a directory.
+/// 
 /// ## Input Parameters
 /// - `file_or_directory` defines the input path to be analysed
 /// 
 /// ## Returns
 /// - True if input path is a directory, else False
-pub fn check_if_directory(file_or_directory: &String) -> bool {
-    // Split given directory path on "/" delimiter
-    let split_dir: Vec<&str> = file_or_directory.split('/').collect::<Vec<&str>>();
-    trace!(target:"other_logging", "split_dir: {:?}", split_dir);
+pub fn check_if_directory(file_or_directory: &str) -> Result<bool, FileOperationsError> {
+    match std::fs::metadata(file_or_directory) {
+        Ok(s) => {
+            trace!(target:"other_logging", "`{}` file type -> {:?}", file_or_directory, s.file_type());
+            Ok(s.is_dir())
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(FileOperationsError::PathNotFoundError(file_or_directory.to_string())),
+        Err(e) => Err(FileOperationsError::FileMetadataReadError(file_or_directory.to_string(), e))
+    }
+}
 
-    // Get last element from split vector
-    let last_item: &str = split_dir[split_dir.len()-1];
-    trace!(target:"other_logging", "last_item: {}", last_item);
+/// Checks if given file is a PDF document by looking for the `%PDF-` header
+/// 
+/// Like most PDF readers, the header is accepted anywhere within the first
+/// 1024 bytes, so files with leading garbage (e.g. mail headers) are detected too.
+/// 
+/// ## Input Parameters
+/// - `file_path` defines the input path to be analysed
+/// 
+/// ## Returns
+/// - True if the file starts with a PDF header, else False
+pub fn is_pdf_file<P: AsRef<Path>>(file_path: P) -> Result<bool, FileOperationsError> {
+    let file_path: &Path = file_path.as_ref();
 
-    // Split last element on "." delimiter
-    let split_item: Vec<&str> = last_item.split('.').collect::<Vec<&str>>();
-    trace!(target:"other_logging", "split_item: {:?}", split_item);
+    let file = match std::fs::File::open(file_path) {
+        Ok(s) => s,
+        Err(e) => return Err(FileOperationsError::FileOpenError(file_path.display().to_string(), e))
+    };
 
-    // If last element doesn't contain any file extension, then it's a directory
-    if split_item.len() == 1 {
-        return true
+    let mut header: Vec<u8> = Vec::with_capacity(PDF_HEADER_SEARCH_LIMIT);
+    if let Err(e) = file.take(PDF_HEADER_SEARCH_LIMIT as u64).read_to_end(&mut header) {
+        return Err(FileOperationsError::FileReadError(file_path.display().to_string(), e));
     }
-    else {
-        return false
+
+    Ok(header.windows(PDF_MAGIC_BYTES.len()).any(|w| w == PDF_MAGIC_BYTES))
+}
+
+/// Builds a glob matcher from a list of glob patterns
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, FileOperationsError> {
+    let mut builder: GlobSetBuilder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(s) => builder.add(s),
+            Err(e) => return Err(FileOperationsError::GlobPatternError(pattern.clone(), e))
+        };
+    }
+
+    match builder.build() {
+        Ok(s) => Ok(s),
+        Err(e) => Err(FileOperationsError::GlobPatternError(patterns.join(", "), e))
+    }
+}
+
+/// Loads the `.seekerignore` file of a directory (if present)
+/// 
+/// ## Input Parameters
+/// - `dir_path` defines the directory whose ignore file is to be loaded
+/// 
+/// ## Returns
+/// - Gitignore matcher rooted at `dir_path`, or None if the directory has no ignore file
+fn load_ignore_file(dir_path: &Path) -> Result<Option<Gitignore>, FileOperationsError> {
+    let ignore_file: PathBuf = dir_path.join(IGNORE_FILE_NAME);
+    if !ignore_file.is_file() {
+        return Ok(None);
+    }
+
+    let mut builder: GitignoreBuilder = GitignoreBuilder::new(dir_path);
+    if let Some(e) = builder.add(&ignore_file) {
+        return Err(FileOperationsError::IgnoreFileParseError(ignore_file.display().to_string(), e));
+    }
+
+    match builder.build() {
+        Ok(s) => {
+            trace!(target:"other_logging", "Loaded {} ignore rule(s) from `{}`", s.num_ignores() + s.num_whitelists(), ignore_file.display());
+            Ok(Some(s))
+        },
+        Err(e) => Err(FileOperationsError::IgnoreFileParseError(ignore_file.display().to_string(), e))
+    }
+}
+
+/// Checks whether a path is ignored by the `.seekerignore` files of its parent directories
+/// 
+/// The ignore file closest to the path takes precedence, so a nested `.seekerignore`
+/// can re-include (`!pattern`) paths ignored further up the tree.
+fn is_ignored(ignore_stack: &[(usize, Gitignore)], path: &Path, is_dir: bool) -> bool {
+    for (_, matcher) in ignore_stack.iter().rev() {
+        match matcher.matched_path_or_any_parents(path, is_dir) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+    }
+
+    false
+}
+
+/// Loads the `.seekerignore` files of all parent directories of a path, outermost first
+///
+/// ## Input Parameters
+/// - `path` defines the canonical file or directory path
+///
+/// ## Returns
+/// - Ignore stack of the parent directories, loaded at depth 0
+fn load_parent_ignore_files(path: &Path) -> Result<Vec<(usize, Gitignore)>, FileOperationsError> {
+    let mut ignore_stack: Vec<(usize, Gitignore)> = Vec::new();
+    let parents: Vec<&Path> = path.ancestors().skip(1).collect();
+    for parent in parents.into_iter().rev() {
+        if let Some(s) = load_ignore_file(parent)? {
+            ignore_stack.push((0, s));
+        }
     }
+
+    Ok(ignore_stack)
 }
 
-/// Gets all file names in a given directory
+/// Gets all PDF file names in a given directory and its subdirectories
+/// 
+/// Files without a `%PDF-` header are ignored. Include and exclude glob patterns
+/// are matched against the path relative to `directory` (e.g. `**/drafts/**`).
+/// Paths matched by a `.seekerignore` file anywhere in the tree, or in a parent directory
+/// of `directory`, are skipped using gitignore semantics and reported separately.
 /// 
 /// ## Input Parameters
 /// - `directory` defines the input path to be analysed
-/// - `track_fail_file` defines the file path for storing failed processing file names
+/// - `traversal_options` defines the maximum depth, symlink handling and include/exclude glob patterns
+/// - `track_fail_file` defines the file path for storing unreadable file or directory names
 /// 
 /// ## Returns
 /// - Vector of file names extracted from directory
-pub fn get_files_in_directory(directory: &String, track_fail_file: Option<&String>) -> Result<Vec<String>, FileOperationsError> {
+/// - Vector of file and directory names skipped due to `.seekerignore` rules
+pub fn get_files_in_directory(directory: &String, traversal_options: &TraversalOptions, track_fail_file: Option<&String>) -> Result<(Vec<String>, Vec<String>), FileOperationsError> {
     let mut files_list: Vec<String> = Vec::new();
+    let mut ignored_list: Vec<String> = Vec::new();
+
+    let include_set: GlobSet = build_glob_set(&traversal_options.include)?;
+    let exclude_set: GlobSet = build_glob_set(&traversal_options.exclude)?;
 
-    // Read contents of the directory
-    let file_paths = match std::fs::read_dir(directory) {
+    // Ignore rules are matched against absolute paths, so that those of parent directories apply too
+    let canonical_dir: PathBuf = match std::fs::canonicalize(directory) {
         Ok(s) => s,
         Err(e) => return Err(FileOperationsError::DirectoryReadError(directory.to_string(), e))
     };
 
-    // Access the files names in directory and push to vector
-    for file in file_paths {
-        let file_name: String = file.unwrap().file_name().into_string().unwrap();
-
-        match std::path::Path::new(&file_name[..])
-        .extension()
-        .and_then(std::ffi::OsStr::to_str) {
-            Some(s) => match &s.to_lowercase()[..] {
-                "pdf" => files_list.push(format!("{}/{}",directory, file_name)),
-                _ => {
-                    match track_fail_file {
-                        Some(s) => {
-                            let error_file: String = format!("{}/{}", &directory, &file_name);
-                            write_to_file(s, &error_file).unwrap()
-                        },
-                        None => {}
-                    }
+    // Ignore rules of the directories on the current path, with the depth they were loaded at
+    let mut ignore_stack: Vec<(usize, Gitignore)> = load_parent_ignore_files(&canonical_dir)?;
+    if is_ignored(&ignore_stack, &canonical_dir, true) {
+        trace!(target:"other_logging", "Skipping `{}` as per {} rules", directory, IGNORE_FILE_NAME);
+        ignored_list.push(directory.to_string());
+        return Ok((files_list, ignored_list));
+    }
+
+    // Walk the directory tree (depth 0 being the directory itself)
+    let mut walker: WalkDir = WalkDir::new(directory)
+        .follow_links(traversal_options.follow_symlinks)
+        .sort_by_file_name();
+    if let Some(s) = traversal_options.max_depth {
+        walker = walker.max_depth(s + 1);
+    }
+
+    let relative_path = |path: &Path| -> String {
+        path.strip_prefix(directory).unwrap_or(path).to_string_lossy().replace('\\', "/")
+    };
+
+    // Access the files names in directory tree and push to vector
+    let mut entries = walker.into_iter();
+    while let Some(entry) = entries.next() {
+        let entry = match entry {
+            Ok(s) => s,
+            Err(e) => {
+                // The directory itself cannot be read
+                if e.depth() == 0 {
+                    return Err(FileOperationsError::DirectoryReadError(directory.to_string(), e.into()));
                 }
+
+                let error_path: String = e.path().map(|p| p.display().to_string()).unwrap_or_default();
+                trace!(target:"other_logging", "Unable to read `{}` -> {}", &error_path, &e);
+                if let Some(s) = track_fail_file {
+                    write_to_file(s, &error_path)?;
+                }
+                continue;
+            }
+        };
+
+        // Drop the ignore rules of directories which are not parents of this entry
+        while entry.depth() > 0 && ignore_stack.last().is_some_and(|(depth, _)| *depth >= entry.depth()) {
+            ignore_stack.pop();
+        }
+        let absolute_path: PathBuf = canonical_dir.join(entry.path().strip_prefix(directory).unwrap_or(entry.path()));
+
+        if entry.file_type().is_dir() {
+            if entry.depth() > 0 {
+                // Skip excluded or ignored subdirectories entirely
+                if exclude_set.is_match(relative_path(entry.path())) {
+                    entries.skip_current_dir();
+                    continue;
+                }
+                if is_ignored(&ignore_stack, &absolute_path, true) {
+                    trace!(target:"other_logging", "Skipping `{}` as per {} rules", entry.path().display(), IGNORE_FILE_NAME);
+                    ignored_list.push(entry.path().display().to_string());
+                    entries.skip_current_dir();
+                    continue;
+                }
+            }
+
+            if let Some(s) = load_ignore_file(&absolute_path)? {
+                ignore_stack.push((entry.depth(), s));
+            }
+            continue;
+        }
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative_file: String = relative_path(entry.path());
+        if !traversal_options.include.is_empty() && !include_set.is_match(&relative_file) {
+            continue;
+        }
+        if exclude_set.is_match(&relative_file) {
+            continue;
+        }
+
+        // Ignored files are skipped before being opened
+        if is_ignored(&ignore_stack, &absolute_path, false) {
+            trace!(target:"other_logging", "Skipping `{}` as per {} rules", entry.path().display(), IGNORE_FILE_NAME);
+            ignored_list.push(entry.path().display().to_string());
+            continue;
+        }
+
+        // Only PDF documents are collected, irrespective of their file extension
+        match is_pdf_file(entry.path()) {
+            Ok(true) => {},
+            Ok(false) => continue,
+            Err(e) => {
+                trace!(target:"other_logging", "{}", &e);
+                if let Some(s) = track_fail_file {
+                    write_to_file(s, &entry.path().display().to_string())?;
+                }
+                continue;
+            }
+        }
+
+        files_list.push(entry.path().display().to_string());
+    }
+
+    Ok((files_list, ignored_list))
+}
+
+/// Checks if given input contains glob metacharacters
+fn is_glob_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '[', '{'])
+}
+
+/// Splits a path into its steps, skipping `.` steps and marking absolute paths with a leading `/` step
+/// 
+/// ## Input Parameters
+/// - `path` defines the file or directory path
+/// 
+/// ## Returns
+/// - Vector of path steps, e.g. `["data", "reports", "a.pdf"]` for `./data/reports/a.pdf`
+pub fn path_steps(path: &str) -> Vec<String> {
+    Path::new(path)
+        .components()
+        .filter(|s| !matches!(s, Component::CurDir))
+        .map(|s| match s {
+            Component::RootDir => String::from("/"),
+            _ => s.as_os_str().to_string_lossy().to_string()
+        })
+        .collect()
+}
+
+/// Splits a glob pattern into the steps of its literal base directory and the glob part
+/// 
+/// ## Input Parameters
+/// - `pattern` defines the glob pattern, e.g. `reports/2024-*.pdf`
+/// 
+/// ## Returns
+/// - Vector of base directory steps (see `path_steps`), e.g. `["reports"]`
+/// - Glob part of the pattern, relative to the base directory, e.g. `2024-*.pdf`
+pub fn split_glob_pattern(pattern: &str) -> (Vec<String>, String) {
+    let mut base_steps: Vec<String> = Vec::new();
+    let mut glob_parts: Vec<String> = Vec::new();
+    for step in path_steps(pattern) {
+        if glob_parts.is_empty() && !is_glob_pattern(&step) {
+            base_steps.push(step);
+        }
+        else {
+            glob_parts.push(step);
+        }
+    }
+
+    (base_steps, glob_parts.join("/"))
+}
+
+/// Builds a glob matcher where `*` doesn't cross directory boundaries
+fn build_path_matcher(pattern: &str, glob_pattern: &str) -> Result<GlobMatcher, FileOperationsError> {
+    match GlobBuilder::new(glob_pattern).literal_separator(true).build() {
+        Ok(s) => Ok(s.compile_matcher()),
+        Err(e) => Err(FileOperationsError::GlobPatternError(pattern.to_string(), e))
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Defines which indexed PDF file paths are covered by a single input, without reading the file system
+/// 
+/// The input is matched the same way `resolve_input_paths` matches it against the files on disk:
+/// a file path covers itself, a directory covers the PDF files below it (subject to the maximum depth
+/// and include/exclude glob patterns) and a glob pattern covers the paths it matches.
+/// `.seekerignore` rules are not applied, as ignored files are never indexed in the first place
+/// (they are skipped for every kind of input, including the ignore files of parent directories).
+pub struct PathScope {
+    base_steps: Vec<String>,
+    matcher: Option<GlobMatcher>,
+    max_depth: Option<usize>,
+    include_set: Option<GlobSet>,
+    exclude_set: GlobSet,
+}
+
+impl PathScope {
+    /// Creates the scope of an input
+    /// 
+    /// ## Input Parameters
+    /// - `input` defines the PDF file, directory or glob pattern
+    /// - `traversal_options` defines the maximum depth and include/exclude glob patterns of directories
+    /// 
+    /// ## Returns
+    /// - `PathScope` of the input
+    pub fn new(input: &str, traversal_options: &TraversalOptions) -> Result<Self, FileOperationsError> {
+        let (base_steps, matcher) = if is_glob_pattern(input) && !Path::new(input).exists() {
+            let (base_steps, glob_pattern) = split_glob_pattern(input);
+            (base_steps, Some(build_path_matcher(input, &glob_pattern)?))
+        } else {
+            (path_steps(input), None)
+        };
+
+        Ok(PathScope {
+            base_steps,
+            matcher,
+            max_depth: traversal_options.max_depth,
+            include_set: if traversal_options.include.is_empty() { None } else { Some(build_glob_set(&traversal_options.include)?) },
+            exclude_set: build_glob_set(&traversal_options.exclude)?,
+        })
+    }
+
+    /// Gets the steps of the directory (or file) all covered paths are found at
+    pub fn base_steps(&self) -> &[String] {
+        &self.base_steps
+    }
+
+    /// Checks if an indexed file path is covered by the input
+    /// 
+    /// ## Input Parameters
+    /// - `path` defines the indexed PDF file path
+    /// 
+    /// ## Returns
+    /// - Boolean value indicating if the path is covered
+    pub fn contains(&self, path: &str) -> bool {
+        let steps: Vec<String> = path_steps(path);
+        if !steps.starts_with(&self.base_steps) {
+            return false;
+        }
+        let relative_steps: &[String] = &steps[self.base_steps.len()..];
+
+        if let Some(s) = &self.matcher {
+            return s.is_match(relative_steps.join("/"));
+        }
+
+        // The input is the file itself
+        if relative_steps.is_empty() {
+            return true;
+        }
+
+        // The input is a directory
+        if self.max_depth.is_some_and(|s| relative_steps.len() > s + 1) {
+            return false;
+        }
+        let relative_file: String = relative_steps.join("/");
+        if self.include_set.as_ref().is_some_and(|s| !s.is_match(&relative_file)) {
+            return false;
+        }
+
+        // Files below an excluded subdirectory are never collected
+        !(1..=relative_steps.len()).any(|s| self.exclude_set.is_match(relative_steps[..s].join("/")))
+    }
+}
+
+/// Expands a glob pattern (e.g. `reports/2024-*.pdf`) into the PDF files it matches
+/// 
+/// The literal leading directories of the pattern are used as the traversal root,
+/// and `*` doesn't cross directory boundaries (use `**` for recursive matching).
+/// Matches ignored by the `.seekerignore` files of the traversal root, its parent
+/// directories or its subdirectories are skipped and reported separately.
+/// 
+/// ## Input Parameters
+/// - `pattern` defines the glob pattern to be expanded
+/// - `follow_symlinks` defines whether symbolic links are followed during traversal
+/// - `track_fail_file` defines the file path for storing unreadable file or directory names
+/// 
+/// ## Returns
+/// - Vector of PDF file names matching the pattern
+/// - Vector of file and directory names skipped due to `.seekerignore` rules
+fn expand_glob_pattern(pattern: &str, follow_symlinks: bool, track_fail_file: Option<&String>) -> Result<(Vec<String>, Vec<String>), FileOperationsError> {
+    let mut files_list: Vec<String> = Vec::new();
+    let mut ignored_list: Vec<String> = Vec::new();
+
+    // Split the pattern into its literal base directory and the glob part
+    let (base_steps, glob_pattern) = split_glob_pattern(pattern);
+    let base_dir: PathBuf = base_steps.iter().collect();
+    trace!(target:"other_logging", "`{}` split into base directory `{}` and glob `{}`", pattern, base_dir.display(), &glob_pattern);
+
+    let matcher: GlobMatcher = build_path_matcher(pattern, &glob_pattern)?;
+
+    // Only descend as deep as the pattern can match, unless it contains `**`
+    let walk_root: &Path = if base_dir.as_os_str().is_empty() { Path::new(".") } else { base_dir.as_path() };
+    let mut walker: WalkDir = WalkDir::new(walk_root)
+        .follow_links(follow_symlinks)
+        .sort_by_file_name();
+    if !glob_pattern.contains("**") {
+        walker = walker.max_depth(glob_pattern.split('/').count());
+    }
+
+    // A pattern whose base directory doesn't exist simply matches nothing
+    let canonical_root: PathBuf = match std::fs::canonicalize(walk_root) {
+        Ok(s) => s,
+        Err(e) => {
+            trace!(target:"other_logging", "Unable to read `{}` -> {}", walk_root.display(), &e);
+            return Ok((files_list, ignored_list));
+        }
+    };
+    let mut ignore_stack: Vec<(usize, Gitignore)> = load_parent_ignore_files(&canonical_root)?;
+
+    let mut entries = walker.into_iter();
+    while let Some(entry) = entries.next() {
+        let entry = match entry {
+            Ok(s) => s,
+            Err(e) => {
+                if e.depth() == 0 {
+                    trace!(target:"other_logging", "Unable to read `{}` -> {}", walk_root.display(), &e);
+                    break;
+                }
+
+                let error_path: String = e.path().map(|p| p.display().to_string()).unwrap_or_default();
+                trace!(target:"other_logging", "Unable to read `{}` -> {}", &error_path, &e);
+                if let Some(s) = track_fail_file {
+                    write_to_file(s, &error_path)?;
+                }
+                continue;
+            }
+        };
+
+        // Drop the ignore rules of directories which are not parents of this entry
+        while entry.depth() > 0 && ignore_stack.last().is_some_and(|(depth, _)| *depth >= entry.depth()) {
+            ignore_stack.pop();
+        }
+        let relative_file: &Path = entry.path().strip_prefix(walk_root).unwrap_or(entry.path());
+        let absolute_path: PathBuf = canonical_root.join(relative_file);
+
+        if entry.file_type().is_dir() {
+            if is_ignored(&ignore_stack, &absolute_path, true) {
+                trace!(target:"other_logging", "Skipping `{}` as per {} rules", entry.path().display(), IGNORE_FILE_NAME);
+                ignored_list.push(entry.path().display().to_string());
+                entries.skip_current_dir();
+                continue;
+            }
+
+            if let Some(s) = load_ignore_file(&absolute_path)? {
+                ignore_stack.push((entry.depth(), s));
+            }
+            continue;
+        }
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        if !matcher.is_match(relative_file) {
+            continue;
+        }
+
+        if is_ignored(&ignore_stack, &absolute_path, false) {
+            trace!(target:"other_logging", "Skipping `{}` as per {} rules", entry.path().display(), IGNORE_FILE_NAME);
+            ignored_list.push(entry.path().display().to_string());
+            continue;
+        }
+
+        match is_pdf_file(entry.path()) {
+            Ok(true) => {},
+            Ok(false) => continue,
+            Err(e) => {
+                trace!(target:"other_logging", "{}", &e);
+                if let Some(s) = track_fail_file {
+                    write_to_file(s, &entry.path().display().to_string())?;
+                }
+                continue;
+            }
+        }
+
+        // Keep relative patterns relative, so that `*.pdf` yields `a.pdf` instead of `./a.pdf`
+        if base_dir.as_os_str().is_empty() {
+            files_list.push(relative_file.display().to_string());
+        }
+        else {
+            files_list.push(entry.path().display().to_string());
+        }
+    }
+
+    Ok((files_list, ignored_list))
+}
+
+/// Resolves a set of input paths into the PDF files to be processed
+/// 
+/// Each input can be a single PDF file, a directory (traversed as per `traversal_options`),
+/// or a glob pattern such as `reports/2024-*.pdf`. Files reachable through several inputs
+/// are only returned once, in the order they were first seen. Inputs of every kind are checked
+/// against the `.seekerignore` files of their parent directories.
+/// 
+/// ## Input Parameters
+/// - `inputs` defines the PDF files, directories or glob patterns to be resolved
+/// - `traversal_options` defines the maximum depth, symlink handling and include/exclude glob patterns
+/// - `track_fail_file` defines the file path for storing unreadable file or directory names
+/// 
+/// ## Returns
+/// - Vector of PDF file names
+/// - Vector of file and directory names skipped due to `.seekerignore` rules
+pub fn resolve_input_paths(inputs: &[String], traversal_options: &TraversalOptions, track_fail_file: Option<&String>) -> Result<(Vec<String>, Vec<String>), FileOperationsError> {
+    let mut files_list: Vec<String> = Vec::new();
+    let mut ignored_list: Vec<String> = Vec::new();
+    let mut seen_files: HashSet<String> = HashSet::new();
+
+    for input in inputs {
+        let input_files: Vec<String> = match check_if_directory(input) {
+            Ok(true) => {
+                trace!(target:"other_logging", "Received `{}` which is directory.", input);
+                let (input_files, input_ignored) = get_files_in_directory(input, traversal_options, track_fail_file)?;
+                ignored_list.extend(input_ignored);
+                input_files
             },
-            None => {}
+            Ok(false) => {
+                trace!(target:"other_logging", "Received `{}` which is file.", input);
+                if !is_pdf_file(input)? {
+                    return Err(FileOperationsError::NotPDFFileError(input.to_string()));
+                }
+
+                // Explicit files are subject to the ignore files of their parent directories
+                let canonical_file: PathBuf = match std::fs::canonicalize(input) {
+                    Ok(s) => s,
+                    Err(e) => return Err(FileOperationsError::FileMetadataReadError(input.to_string(), e))
+                };
+                if is_ignored(&load_parent_ignore_files(&canonical_file)?, &canonical_file, false) {
+                    trace!(target:"other_logging", "Skipping `{}` as per {} rules", input, IGNORE_FILE_NAME);
+                    ignored_list.push(input.to_string());
+                    continue;
+                }
+                vec![input.to_string()]
+            },
+            Err(FileOperationsError::PathNotFoundError(_)) if is_glob_pattern(input) => {
+                trace!(target:"other_logging", "Received `{}` which is glob pattern.", input);
+                let (input_files, input_ignored) = expand_glob_pattern(input, traversal_options.follow_symlinks, track_fail_file)?;
+                ignored_list.extend(input_ignored);
+                if input_files.is_empty() {
+                    warn!(target:"other_logging", "Glob pattern `{}` didn't match any PDF file.", input);
+                }
+                input_files
+            },
+            Err(e) => return Err(e)
         };
+
+        for file in input_files {
+            if seen_files.insert(file.clone()) {
+                files_list.push(file);
+            }
+        }
     }
 
-    Ok(files_list)
+    Ok((files_list, ignored_list))
+}
+
+/// Reads a newline-separated list of input paths from a file, or from stdin if `list_file` is `-`
+/// 
+/// Blank lines and lines starting with `#` are skipped.
+/// 
+/// ## Input Parameters
+/// - `list_file` defines the file containing one input path per line
+/// 
+/// ## Returns
+/// - Vector of input paths
+pub fn read_path_list(list_file: &str) -> Result<Vec<String>, FileOperationsError> {
+    let lines: Vec<String> = if list_file == "-" {
+        match std::io::stdin().lock().lines().collect() {
+            Ok(s) => s,
+            Err(e) => return Err(FileOperationsError::FileReadError(String::from("<stdin>"), e))
+        }
+    }
+    else {
+        read_from_file(&list_file.to_string())?
+    };
+
+    Ok(lines
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty() && !s.starts_with('#'))
+        .map(String::from)
+        .collect())
 }
 
 /// Return the cache directory if provided, else return the current working directory
@@ -132,6 +1003,7 @@ pub fn get_files_in_directory(directory: &String, track_fail_file: Option<&Strin
 /// 
 /// ## Returns
 /// - Cache directory or current working directory
+#[allow(clippy::to_string_in_format_args)]
 pub fn get_cache_dir(cache_path: &Option<String>) -> Result<String, FileOperationsError> {
     let cache_dir: String = match cache_path {
         Some(s) => s.clone(),
@@ -153,6 +1025,7 @@ pub fn get_cache_dir(cache_path: &Option<String>) -> Result<String, FileOperatio
 /// 
 /// ## Returns
 /// - None
+#[allow(clippy::needless_borrow)]
 pub fn create_cache_dir_if_not_exists(cache_path: &Option<String>) -> Result<String, FileOperationsError> {
     // Get cache directory path
     let cache_dir: String = get_cache_dir(&cache_path)?;
@@ -225,7 +1098,10 @@ pub fn read_from_file(file_path: &String) -> Result<Vec<String>, FileOperationsE
         Err(e) => return Err(FileOperationsError::FileOpenError(file_path.clone(), e))
     };
 
-    let file_contents: Vec<String> = std::io::BufReader::new(file).lines().map(|l| l.unwrap()).collect();
+    let file_contents: Vec<String> = match std::io::BufReader::new(file).lines().collect() {
+        Ok(s) => s,
+        Err(e) => return Err(FileOperationsError::FileReadError(file_path.clone(), e))
+    };
     let mut processed_files: Vec<String> = Vec::new();
 
     for file in file_contents {
@@ -233,4 +1109,14 @@ pub fn read_from_file(file_path: &String) -> Result<Vec<String>, FileOperationsE
     }
 
     Ok(processed_files)
-}
\ No newline at end of file
+}
+
+#[pymethods]
+impl TraversalOptions {
+    #[new]
+    #[pyo3(signature = (max_depth=None, follow_symlinks=false, include=Vec::new(), exclude=Vec::new()))]
+    /// Creates the directory traversal configuration
+    fn py_new(max_depth: Option<usize>, follow_symlinks: bool, include: Vec<String>, exclude: Vec<String>) -> Self {
+        TraversalOptions { max_depth, follow_symlinks, include, exclude }
+    }
+}
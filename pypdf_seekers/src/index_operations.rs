@@ -2,24 +2,229 @@
 //! 
 //! - Defines the supporting functions for parsing and indexing PDF files
 //! - Creates the directory for storing indexed files, if it doesn't exist
+//! - Detects changes to already indexed PDF files based on their fingerprints
+//! - Purges index documents for PDF files which no longer exist on disk
+//! - Extracts PDF files in parallel and feeds a single shared index writer
+//! - Stores the text of identical PDF files only once, recording every path they were found at
+//! - Stores the detected language of every page, routing its text to a language-specific content field if enabled
+//! - Indexes the directory hierarchy of every PDF file path, so that searches can be scoped to directories
 
-use log::{info, debug, error, trace};
+use log::{info, debug, error, trace, warn};
+use std::collections::{HashMap, HashSet};
 use crate::file_operations::*;
+use crate::analyzer_operations::*;
+use crate::language_operations::{content_field_name, SUPPORTED_LANGUAGES};
 use crate::error::IndexingError;
-use tantivy::{Index, IndexWriter, Document};
-use tantivy::schema::{SchemaBuilder, TEXT, STORED, STRING};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::Instant;
+use tracing::{info_span, Span};
+use tracing::field::Empty;
+use tantivy::{DateTime, Index, IndexWriter, Document, Searcher, Term};
+use tantivy::merge_policy::LogMergePolicy;
+use tantivy::collector::{Count, DocSetCollector, TopDocs};
+use tantivy::query::{BooleanQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Facet, FacetOptions, Field, Schema, SchemaBuilder, IndexRecordOption, TextFieldIndexing, TextOptions, STORED, STRING, INDEXED, FAST};
+use pyo3::prelude::*;
 
-const NUM_THREADS: usize = 1;
-const OVERALL_MEMORY_ARENA_IN_BYTES: usize = 1 << 30; // 1GiB
+/// Version of the index schema, increased whenever fields are added, removed or change type
+pub const SCHEMA_VERSION: u32 = 1;
+/// File in the index directory storing the schema version the index was created with
+pub const SCHEMA_VERSION_FILE: &str = "schema_version.txt";
+
+/// Kind of index documents recording the path and fingerprint of a PDF file
+pub const DOC_KIND_FILE: &str = "file";
+/// Kind of index documents holding the text of a single page of a unique PDF document
+pub const DOC_KIND_PAGE: &str = "page";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Defines the outcome of indexing a single PDF file
+pub enum IndexingStatus {
+    /// PDF file was indexed for the first time
+    Indexed,
+    /// PDF file was changed since the last run and its index documents were replaced
+    Reindexed,
+    /// PDF file is unchanged since the last run and was skipped
+    Unchanged,
+    /// PDF file has the same content as an already indexed PDF file, so only its path was recorded
+    Duplicate,
+    /// PDF file could not be read or indexed
+    Failed,
+}
+
+#[pyclass]
+#[derive(Debug, Clone)]
+/// Defines the configuration of an indexing run
+pub struct IndexingOptions {
+    #[pyo3(get)]
+    /// Number of worker threads extracting text from PDF files in parallel
+    pub num_workers: usize,
+
+    #[pyo3(get)]
+    /// Number of indexing threads used by the shared Tantivy index writer
+    pub writer_threads: usize,
+
+    #[pyo3(get)]
+    /// Overall memory budget (in bytes) of the shared Tantivy index writer, split across its threads
+    pub writer_memory_bytes: usize,
+
+    #[pyo3(get)]
+    /// Number of indexed PDF files after which changes are committed to the index
+    pub commit_batch_size: usize,
+
+    #[pyo3(get)]
+    /// Text analyzer used if the index is created by this run (ignored for existing indexes, which keep their analyzer)
+    pub analyzer: Option<AnalyzerOptions>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Defines the size of an index
+pub struct IndexStats {
+    /// Number of indexed PDF file paths
+    pub num_files: usize,
+    /// Number of unique PDF contents (identical PDF files are stored once)
+    pub num_contents: usize,
+    /// Number of indexed pages, across all unique PDF contents
+    pub num_pages: usize,
+    /// Number of index segments
+    pub num_segments: usize,
+}
+
+impl Default for IndexingOptions {
+    fn default() -> Self {
+        let num_cpus: usize = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        IndexingOptions {
+            num_workers: num_cpus,
+            writer_threads: std::cmp::min(num_cpus, 4),
+            writer_memory_bytes: 512 << 20, // 512MiB
+            commit_batch_size: 100,
+            analyzer: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Defines the page contents extracted from a single PDF file
+pub struct PdfContents {
+    /// Page numbers of the PDF file
+    pub pdf_page_nums: Vec<u32>,
+    /// Extracted text of every page
+    pub pdf_texts: Vec<String>,
+    /// Detected language of every page (None if not detected, or empty if languages weren't detected)
+    pub pdf_languages: Vec<Option<String>>,
+    /// Document-level metadata of the PDF file
+    pub document_info: DocumentInfo,
+}
+
+impl From<PdfPages> for PdfContents {
+    fn from((pdf_page_nums, pdf_texts, pdf_languages, document_info): PdfPages) -> Self {
+        PdfContents { pdf_page_nums, pdf_texts, pdf_languages, document_info }
+    }
+}
+
+/// Defines a single PDF file read by an extraction worker
+struct ExtractedPdf {
+    fingerprint: FileFingerprint,
+    /// Content hash indexed for the PDF file in earlier runs (None if indexed for the first time)
+    previous_hash: Option<String>,
+    /// Page contents of the PDF file (None if its content is already indexed under another path)
+    contents: Option<PdfContents>,
+}
+
+/// Defines the outcome of reading a single PDF file by an extraction worker
+enum ExtractedFile {
+    /// PDF file is unchanged since the last run
+    Unchanged,
+    /// PDF file content is unchanged since the last run, but its size or modification time changed (e.g. after a `touch`)
+    Touched(FileFingerprint),
+    /// PDF file could not be read
+    Failed,
+    /// PDF file was read and is ready to be added to the index
+    Ready(Box<ExtractedPdf>),
+}
+
+/// Gets the facet recording the directory hierarchy of a path, e.g. `/data/reports/a.pdf` for `./data/reports/a.pdf`
+/// 
+/// Every directory of the path is indexed as a parent facet, so that a facet term query
+/// on a directory matches all PDF files below it (and a query on a file matches the file).
+/// Absolute paths start with a `/` step, so they never match relative ones.
+/// 
+/// ## Input Parameters
+/// - `path` defines the file or directory path
+/// 
+/// ## Returns
+/// - Facet of the path
+pub fn path_facet(path: &str) -> Facet {
+    Facet::from_path(path_steps(path))
+}
+
+/// Gets a field from the index schema
+fn get_field(schema: &Schema, field_name: &str) -> Result<Field, IndexingError> {
+    match schema.get_field(field_name) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(IndexingError::IndexFieldNotFound(field_name.to_string(), e))
+    }
+}
 
 /// Creates or opens the directory to be used for storing indexed files
 /// 
+/// New indexes are created with the default text analyzer, while existing
+/// indexes keep the analyzer they were created with.
+/// 
 /// ## Input Parameters
 /// - `index_path` defines the input path for storing the indexed files
 /// 
 /// ## Returns
 /// - Tantivy index for performing keyword search on PDF files
 pub fn create_or_open_index(index_path: &str) -> Result<Index, IndexingError> {
+    create_or_open_index_with_analyzer(index_path, None)
+}
+
+/// Stores the current schema version in the index directory
+fn save_schema_version(index_path: &str) -> Result<(), IndexingError> {
+    let version_file: String = format!("{}/{}", index_path, SCHEMA_VERSION_FILE);
+
+    match std::fs::write(&version_file, SCHEMA_VERSION.to_string()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(IndexingError::SchemaVersionWriteError(version_file, e))
+    }
+}
+
+/// Checks that the index directory was created with the current schema version
+/// 
+/// Indexes created before the schema version was stored are reported as version 0.
+fn check_schema_version(index_path: &str) -> Result<(), IndexingError> {
+    let version_file: String = format!("{}/{}", index_path, SCHEMA_VERSION_FILE);
+
+    let stored_version: u32 = match std::fs::read_to_string(&version_file) {
+        Ok(s) => s.trim().parse::<u32>().unwrap_or_default(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(IndexingError::SchemaVersionReadError(version_file, e))
+    };
+    trace!(target:"other_logging", "`{}` -> schema version {} (required: {})", index_path, stored_version, SCHEMA_VERSION);
+
+    if stored_version != SCHEMA_VERSION {
+        return Err(IndexingError::IndexRebuildRequired(index_path.to_string(), stored_version, SCHEMA_VERSION));
+    }
+
+    Ok(())
+}
+
+/// Creates or opens the directory to be used for storing indexed files, using a given text analyzer
+/// 
+/// The analyzer configuration is stored with a newly created index. When an existing
+/// index is opened, its stored analyzer is always used, so that documents and queries
+/// are tokenized the same way; a different requested configuration is ignored with a warning.
+/// The schema version is stored as well, and an existing index created with another schema
+/// version is rejected with `IndexRebuildRequired` instead of failing on missing fields later.
+/// 
+/// ## Input Parameters
+/// - `index_path` defines the input path for storing the indexed files
+/// - `analyzer_options` defines the text analyzer for a new index (default analyzer if not provided)
+/// 
+/// ## Returns
+/// - Tantivy index for performing keyword search on PDF files
+pub fn create_or_open_index_with_analyzer(index_path: &str, analyzer_options: Option<&AnalyzerOptions>) -> Result<Index, IndexingError> {
     // Create the directory for storing indexed files (if doesn't exist)
     match std::fs::create_dir_all(index_path) {
         Ok(_) => {
@@ -38,23 +243,75 @@ pub fn create_or_open_index(index_path: &str) -> Result<Index, IndexingError> {
     debug!(target:"other_logging", "Is index directory `{}` empty? -> {}", index_path, dir_check);
 
     let index: Index = if !dir_check {
+        // Indexes created with another schema can't be read or updated
+        check_schema_version(index_path)?;
+
         // Open the index directory to build the Tantivy index (if directory is not empty)
-        match Index::open_in_dir(index_path) {
+        let index: Index = match Index::open_in_dir(index_path) {
             Ok(s) => {
                 info!(target:"info_logging", "{} - Directory content read successfully.", &index_path);
                 s
             },
             Err(e) => return Err(IndexingError::IndexDirectoryOpenError(index_path.to_string(), e))
+        };
+
+        // Tokenize with the analyzer the index was created with
+        let stored_options: AnalyzerOptions = load_analyzer_options(index_path)?;
+        if let Some(s) = analyzer_options {
+            if *s != stored_options {
+                warn!(target:"other_logging", "Index `{}` was created with analyzer {:?}. Ignoring requested analyzer {:?}.", index_path, &stored_options, s);
+            }
         }
+        register_analyzer(&index, &stored_options)?;
+
+        index
     } else {
         info!(target:"info_logging", "{} - Directory is empty.", &index_path);
 
+        // Build the analyzer upfront, so that invalid configurations don't leave an index behind
+        let analyzer_options: AnalyzerOptions = analyzer_options.cloned().unwrap_or_default();
+        build_text_analyzer(&analyzer_options)?;
+
+        // Text fields are tokenized by the configured analyzer
+        let text_options: TextOptions = TextOptions::default()
+            .set_indexing_options(TextFieldIndexing::default()
+                .set_tokenizer(ANALYZER_NAME)
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions))
+            .set_stored();
+
         let mut schema_builder: SchemaBuilder = SchemaBuilder::new();
 
-        // Add fields to the schema
-        schema_builder.add_text_field("content", TEXT | STORED);
+        // Add fields to the schema (`doc_kind` separates file location documents from page documents)
+        schema_builder.add_text_field("doc_kind", STRING | STORED);
+        schema_builder.add_text_field("content", text_options.clone());
         schema_builder.add_text_field("path", STRING | STORED);
-        schema_builder.add_text_field("page_num", STRING | STORED);
+        schema_builder.add_facet_field("path_facet", FacetOptions::default());
+        schema_builder.add_u64_field("page_num", INDEXED | STORED | FAST);
+        schema_builder.add_u64_field("num_pages", STORED);
+        schema_builder.add_u64_field("file_size", STORED);
+        schema_builder.add_u64_field("file_modified", STORED);
+        schema_builder.add_text_field("base_dir", STORED);
+        schema_builder.add_text_field("content_hash", STRING | STORED);
+        schema_builder.add_text_field("title", text_options.clone());
+        schema_builder.add_text_field("author", text_options.clone());
+        schema_builder.add_text_field("subject", text_options.clone());
+        schema_builder.add_text_field("keywords", text_options.clone());
+        schema_builder.add_text_field("producer", text_options.clone());
+        schema_builder.add_date_field("creation_date", INDEXED | STORED);
+        schema_builder.add_date_field("mod_date", INDEXED | STORED);
+        schema_builder.add_text_field("language", STRING | STORED);
+
+        // Add one content field per supported language, tokenized by an analyzer for that language
+        if analyzer_options.detect_language {
+            for language in SUPPORTED_LANGUAGES {
+                let language_options: TextOptions = TextOptions::default()
+                    .set_indexing_options(TextFieldIndexing::default()
+                        .set_tokenizer(&language_analyzer_name(language))
+                        .set_index_option(IndexRecordOption::WithFreqsAndPositions))
+                    .set_stored();
+                schema_builder.add_text_field(&content_field_name(language), language_options);
+            }
+        }
 
         // Build the Tantivy index (if index directory is empty)
         let index: Index = match Index::builder()
@@ -64,15 +321,10 @@ pub fn create_or_open_index(index_path: &str) -> Result<Index, IndexingError> {
                 Err(e) => return Err(IndexingError::IndexCreateError(index_path.to_string(), e))
             };
 
-        // Set up the index writer
-        let index_writer: IndexWriter = match index
-            .writer_with_num_threads(NUM_THREADS, OVERALL_MEMORY_ARENA_IN_BYTES) { // 50MB heap size for indexing
-                Ok(s) => s,
-                Err(e) => return Err(IndexingError::IndexWriterCreateError(e))
-            };
-
-        // Close the index writer
-        drop(index_writer);
+        // Store the analyzer configuration with the index, and use it for tokenization
+        register_analyzer(&index, &analyzer_options)?;
+        save_analyzer_options(index_path, &analyzer_options)?;
+        save_schema_version(index_path)?;
 
         index
     };
@@ -80,105 +332,915 @@ pub fn create_or_open_index(index_path: &str) -> Result<Index, IndexingError> {
     Ok(index)
 }
 
-/// Parse a PDF file and create indexes for same in index directory
+/// Reads the fingerprint stored in the index for a PDF file
 /// 
 /// ## Input Parameters
-/// - `pdf_file` contains the PDF file to be parsed and indexed
-/// - `pdf_page_num` contains the matched page numbers in PDF file containing the search term
-/// - `pdf_text` contains the extracted text from PDF file for indexing
-/// - `index` contains the Tantivy index for parsing and indexing
+/// - `pdf_file` contains the PDF file to be looked up
+/// - `index` contains the Tantivy index to be searched
 /// 
 /// ## Returns
-/// - None
-pub fn parse_and_index_pdf(pdf_file: &str, pdf_page_num: Vec<u32>, pdf_text: Vec<String>, index: &Index) -> Result<(), IndexingError> {
-    // Create a Tantivy index writer
-    let mut index_writer = match index
-        .writer_with_num_threads(NUM_THREADS, OVERALL_MEMORY_ARENA_IN_BYTES) {
+/// - Fingerprint of the PDF file at the time of indexing, or None if the file is not indexed
+pub fn get_indexed_fingerprint(pdf_file: &str, index: &Index) -> Result<Option<FileFingerprint>, IndexingError> {
+    // Create the index searcher object
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+
+    read_indexed_fingerprint(pdf_file, &searcher)
+}
+
+/// Reads the fingerprint stored for a PDF file using an existing index searcher
+fn read_indexed_fingerprint(pdf_file: &str, searcher: &Searcher) -> Result<Option<FileFingerprint>, IndexingError> {
+    let schema = searcher.schema();
+    let path_field = get_field(schema, "path")?;
+    let file_size_field = get_field(schema, "file_size")?;
+    let file_modified_field = get_field(schema, "file_modified")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+
+    // Look up the file location document indexed for the PDF file
+    let query = TermQuery::new(Term::from_field_text(path_field, pdf_file), IndexRecordOption::Basic);
+    let top_docs = match searcher.search(&query, &TopDocs::with_limit(1)) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(pdf_file.to_string(), e))
+    };
+
+    let doc_address = match top_docs.first() {
+        Some((_, s)) => *s,
+        None => return Ok(None)
+    };
+
+    let retrieved_doc = match searcher.doc(doc_address) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(pdf_file.to_string(), e))
+    };
+
+    Ok(Some(FileFingerprint {
+        size: retrieved_doc.get_first(file_size_field).and_then(|v| v.as_u64()).unwrap_or_default(),
+        modified: retrieved_doc.get_first(file_modified_field).and_then(|v| v.as_u64()).unwrap_or_default(),
+        content_hash: retrieved_doc
+            .get_first(content_hash_field)
+            .and_then(|v| v.as_text())
+            .unwrap_or_default()
+            .to_string()
+    }))
+}
+
+/// Counts the documents of a given kind indexed for a content hash
+fn count_content_documents(content_hash: &str, doc_kind: &str, searcher: &Searcher) -> Result<usize, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, doc_kind), IndexRecordOption::Basic)) as Box<dyn Query>),
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(content_hash_field, content_hash), IndexRecordOption::Basic))),
+    ]);
+
+    match searcher.search(&query, &Count) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(IndexingError::IndexDocumentFetchError(content_hash.to_string(), e))
+    }
+}
+
+/// Lists the paths of the PDF files indexed for a content hash
+fn content_file_paths(content_hash: &str, searcher: &Searcher) -> Result<Vec<String>, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+    let path_field = get_field(schema, "path")?;
+
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_FILE), IndexRecordOption::Basic)) as Box<dyn Query>),
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(content_hash_field, content_hash), IndexRecordOption::Basic))),
+    ]);
+    let file_docs = match searcher.search(&query, &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(content_hash.to_string(), e))
+    };
+
+    let mut paths: Vec<String> = Vec::new();
+    for doc_address in file_docs {
+        let retrieved_doc = match searcher.doc(doc_address) {
             Ok(s) => s,
-            Err(e) => return Err(IndexingError::IndexWriterCreateError(e))
+            Err(e) => return Err(IndexingError::IndexDocumentFetchError(content_hash.to_string(), e))
         };
+        paths.push(retrieved_doc.get_first(path_field).and_then(|v| v.as_text()).unwrap_or_default().to_string());
+    }
 
-    // Prevent any segment merge, again to control the number of segments.
-    index_writer.set_merge_policy(Box::new(tantivy::merge_policy::NoMergePolicy));
+    Ok(paths)
+}
 
-    // Create a Tantivy document
-    let mut doc = Document::default();
+/// Deletes the page documents of content hashes which are no longer referenced by any PDF file, without committing
+/// 
+/// References are looked up in the committed index, where the uncommitted locations in
+/// `pending_locations` (indexed path to its new content hash, or None if removed) replace the
+/// committed ones, so that the deletion becomes visible in the same commit as the changes
+/// dropping the references.
+fn delete_orphaned_contents(content_hashes: &[String], pending_locations: &HashMap<String, Option<String>>, index: &Index, index_writer: &IndexWriter) -> Result<usize, IndexingError> {
+    if content_hashes.is_empty() {
+        return Ok(0);
+    }
 
-    // Add PDF content, path and page number to the index document
-    for (idx, page_num) in pdf_page_num.iter().enumerate() {
-        doc.add_text(match index.schema().get_field("content") {
-            Ok(s) => s,
-            Err(e) => return Err(IndexingError::IndexFieldNotFound(String::from("content"), e))
-        }, &pdf_text[idx]);
+    let content_hash_field = get_field(&index.schema(), "content_hash")?;
+
+    // Snapshot of the index including the committed changes
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+
+    let mut num_purged: usize = 0;
+    for content_hash in content_hashes.iter().collect::<HashSet<&String>>() {
+        if pending_locations.values().any(|s| s.as_ref() == Some(content_hash)) {
+            continue;
+        }
+        if content_file_paths(content_hash, &searcher)?.iter().all(|s| pending_locations.contains_key(s)) {
+            trace!(target:"other_logging", "Content `{}` is no longer referenced by any file. Removing its pages.", content_hash);
+            index_writer.delete_term(Term::from_field_text(content_hash_field, content_hash));
+            num_purged += 1;
+        }
+    }
+
+    Ok(num_purged)
+}
+
+/// Lists the distinct PDF files currently present in the index
+/// 
+/// ## Input Parameters
+/// - `searcher` contains the searcher of the Tantivy index to be analysed
+/// 
+/// ## Returns
+/// - Sorted vector of indexed PDF file paths
+pub fn get_indexed_files(searcher: &Searcher) -> Result<Vec<String>, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let path_field = get_field(schema, "path")?;
 
-        doc.add_text(match index.schema().get_field("page_num") {
+    // Every indexed PDF file has exactly one location document
+    let query = TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_FILE), IndexRecordOption::Basic);
+    let file_docs = match searcher.search(&query, &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+    };
+
+    let mut indexed_files: Vec<String> = Vec::new();
+    for doc_address in file_docs {
+        let retrieved_doc = match searcher.doc(doc_address) {
             Ok(s) => s,
-            Err(e) => return Err(IndexingError::IndexFieldNotFound(String::from("page_num"), e))
-        }, page_num);
+            Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+        };
+        if let Some(s) = retrieved_doc.get_first(path_field).and_then(|v| v.as_text()) {
+            indexed_files.push(s.to_string());
+        }
+    }
 
-        doc.add_text(
-            match index.schema().get_field("path") {
-                Ok(s) => s,
-                Err(e) => return Err(IndexingError::IndexFieldNotFound(String::from("path"), e))
-            },
-            &pdf_file.to_string(),
-        );
+    indexed_files.sort();
+    indexed_files.dedup();
+    Ok(indexed_files)
+}
+
+/// Counts the indexed PDF files, unique contents and pages of an index
+/// 
+/// ## Input Parameters
+/// - `searcher` contains the snapshot of the Tantivy index to be analysed
+/// 
+/// ## Returns
+/// - `IndexStats` struct containing the size of the index
+pub fn get_index_stats(searcher: &Searcher) -> Result<IndexStats, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+
+    let doc_kind_query = |doc_kind: &str| TermQuery::new(Term::from_field_text(doc_kind_field, doc_kind), IndexRecordOption::Basic);
+
+    let file_docs = match searcher.search(&doc_kind_query(DOC_KIND_FILE), &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+    };
+
+    // Identical PDF files share the content hash of their location documents
+    let mut content_hashes: HashSet<String> = HashSet::new();
+    for doc_address in &file_docs {
+        let retrieved_doc = match searcher.doc(*doc_address) {
+            Ok(s) => s,
+            Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+        };
+        if let Some(s) = retrieved_doc.get_first(content_hash_field).and_then(|v| v.as_text()) {
+            content_hashes.insert(s.to_string());
+        }
     }
 
-    // Add the document to the index
-    match index_writer.add_document(doc) {
-        Ok(_) => {},
-        Err(e) => return Err(IndexingError::IndexDocumentAddError(e))
+    let num_pages: usize = match searcher.search(&doc_kind_query(DOC_KIND_PAGE), &Count) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_PAGE), e))
     };
 
+    Ok(IndexStats {
+        num_files: file_docs.len(),
+        num_contents: content_hashes.len(),
+        num_pages,
+        num_segments: searcher.segment_readers().len(),
+    })
+}
+
+/// Reads the text stored in the index for a page of an indexed PDF file
+/// 
+/// ## Input Parameters
+/// - `pdf_file` contains the indexed PDF file path
+/// - `page_num` defines the page number (starting at 1)
+/// - `searcher` contains the snapshot of the Tantivy index to be read
+/// 
+/// ## Returns
+/// - Text of the page, or None if the PDF file is not indexed or has no such page
+pub fn get_page_text(pdf_file: &str, page_num: u32, searcher: &Searcher) -> Result<Option<String>, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+    let page_num_field = get_field(schema, "page_num")?;
+    let content_field = get_field(schema, "content")?;
+    let language_field = get_field(schema, "language")?;
+
+    // Pages are stored once per unique content, so look up the content of the PDF file first
+    let content_hash: String = match read_indexed_fingerprint(pdf_file, searcher)? {
+        Some(s) => s.content_hash,
+        None => return Ok(None)
+    };
+
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_PAGE), IndexRecordOption::Basic)) as Box<dyn Query>),
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(content_hash_field, &content_hash), IndexRecordOption::Basic))),
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_u64(page_num_field, page_num as u64), IndexRecordOption::Basic))),
+    ]);
+    let top_docs = match searcher.search(&query, &TopDocs::with_limit(1)) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(pdf_file.to_string(), e))
+    };
+
+    let doc_address = match top_docs.first() {
+        Some((_, s)) => *s,
+        None => return Ok(None)
+    };
+
+    let retrieved_doc = match searcher.doc(doc_address) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(pdf_file.to_string(), e))
+    };
+
+    // The text is stored in the content field of the page language (if the index has one)
+    let page_content_field: Field = match retrieved_doc
+        .get_first(language_field)
+        .and_then(|v| v.as_text())
+        .map(|s| schema.get_field(&content_field_name(s))) {
+            Some(Ok(s)) => s,
+            _ => content_field
+        };
+
+    Ok(Some(retrieved_doc
+        .get_first(page_content_field)
+        .and_then(|v| v.as_text())
+        .unwrap_or_default()
+        .to_string()))
+}
+
+/// Removes all index documents of the given PDF files
+/// 
+/// The page documents of a PDF file are only removed once no other indexed
+/// path shares the same content.
+/// 
+/// ## Input Parameters
+/// - `pdf_files` contains the PDF files to be removed from the index
+/// - `index` contains the Tantivy index to be updated
+/// 
+/// ## Returns
+/// - None
+pub fn remove_from_index(pdf_files: &[String], index: &Index) -> Result<(), IndexingError> {
+    // Create a Tantivy index writer, with the default writer threads and memory budget
+    let mut index_writer: IndexWriter = create_index_writer(index, &IndexingOptions::default())?;
+
+    remove_from_index_with_writer(pdf_files, index, &mut index_writer)
+}
+
+/// Removes all index documents of the given PDF files using an existing index writer
+/// 
+/// Changes are committed before returning, as with [`remove_from_index`].
+/// 
+/// ## Input Parameters
+/// - `pdf_files` contains the PDF files to be removed from the index
+/// - `index` contains the Tantivy index to be updated
+/// - `index_writer` contains the index writer of `index`
+/// 
+/// ## Returns
+/// - None
+pub fn remove_from_index_with_writer(pdf_files: &[String], index: &Index, index_writer: &mut IndexWriter) -> Result<(), IndexingError> {
+    let path_field = get_field(&index.schema(), "path")?;
+
+    // Snapshot of the index for reading the content hashes of the removed files
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+
+    let mut removed_hashes: Vec<String> = Vec::new();
+    let mut removed_locations: HashMap<String, Option<String>> = HashMap::new();
+    for pdf_file in pdf_files {
+        if let Some(s) = read_indexed_fingerprint(pdf_file, &searcher)? {
+            removed_hashes.push(s.content_hash);
+        }
+        index_writer.delete_term(Term::from_field_text(path_field, pdf_file));
+        removed_locations.insert(pdf_file.clone(), None);
+    }
+
+    // Remove the pages no other PDF file references along with the locations
+    let num_purged: usize = delete_orphaned_contents(&removed_hashes, &removed_locations, index, index_writer)?;
+
     // Commit changes to the index
     match index_writer.commit() {
         Ok(_) => {},
         Err(e) => return Err(IndexingError::IndexDocumentCommitError(e))
     };
+    if num_purged > 0 {
+        debug!(target:"other_logging", "Removed pages of {} unreferenced document content(s).", num_purged);
+    }
 
     Ok(())
 }
 
-/// Parse and index either single PDF file or directory containing multiple PDF files
+/// Gets the directory relative paths of PDF files are resolved against, i.e. the working directory of the process
+fn indexing_base_dir() -> String {
+    match std::env::current_dir().and_then(|s| s.canonicalize()) {
+        Ok(s) => s.to_string_lossy().to_string(),
+        Err(err) => {
+            warn!(target:"other_logging", "Unable to read the current working directory: {}", err);
+            String::new()
+        }
+    }
+}
+
+/// Lists the indexed PDF files which no longer exist on disk
+/// 
+/// Relative paths are resolved against the working directory of the process which indexed
+/// them, so the result doesn't depend on the working directory of the caller.
 /// 
 /// ## Input Parameters
-/// - `file_path` defines the input path for single PDF file or directory containing multiple PDF files
-/// - `index_path` defines the input path for storing the indexed files
-/// - `track_fail_file` defines the file path for storing failed processing file names
+/// - `searcher` contains the snapshot of the Tantivy index to be checked
+/// 
+/// ## Returns
+/// - Sorted vector of indexed PDF file paths missing on disk
+pub fn get_missing_files(searcher: &Searcher) -> Result<Vec<String>, IndexingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let path_field = get_field(schema, "path")?;
+    let base_dir_field = get_field(schema, "base_dir")?;
+
+    let query = TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_FILE), IndexRecordOption::Basic);
+    let file_docs = match searcher.search(&query, &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+    };
+
+    let mut missing_files: Vec<String> = Vec::new();
+    for doc_address in file_docs {
+        let retrieved_doc = match searcher.doc(doc_address) {
+            Ok(s) => s,
+            Err(e) => return Err(IndexingError::IndexDocumentFetchError(String::from(DOC_KIND_FILE), e))
+        };
+
+        let path: &str = retrieved_doc.get_first(path_field).and_then(|v| v.as_text()).unwrap_or_default();
+        let disk_path: std::path::PathBuf = match retrieved_doc.get_first(base_dir_field).and_then(|v| v.as_text()) {
+            Some(s) => std::path::Path::new(s).join(path),
+            None => std::path::PathBuf::from(path)
+        };
+
+        if !disk_path.is_file() {
+            missing_files.push(path.to_string());
+        }
+    }
+
+    missing_files.sort();
+    missing_files.dedup();
+    Ok(missing_files)
+}
+
+/// Purges index documents of PDF files which no longer exist on disk
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index to be synchronised with the filesystem
+/// 
+/// ## Returns
+/// - Vector of PDF file paths removed from the index
+pub fn sync_index(index: &Index) -> Result<Vec<String>, IndexingError> {
+    // Create the index searcher object
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+
+    let missing_files: Vec<String> = get_missing_files(&searcher)?;
+    trace!(target:"other_logging", "Indexed files missing on disk -> {:?}", &missing_files);
+
+    if !missing_files.is_empty() {
+        remove_from_index(&missing_files, index)?;
+    }
+
+    Ok(missing_files)
+}
+
+/// Parse a PDF file and create indexes for same in index directory
+/// 
+/// Every page of the PDF file is added as its own Tantivy document, so that
+/// each page is scored independently at search time. The path of the PDF file
+/// is recorded in a separate location document, and pages are only added if no
+/// other PDF file with the same content hash is indexed yet. Any location indexed
+/// earlier for the same PDF file, and the pages of its previous content no other
+/// PDF file shares, are replaced within the same commit.
+/// 
+/// ## Input Parameters
+/// - `pdf_file` contains the PDF file to be parsed and indexed
+/// - `contents` contains the page numbers, page texts, page languages and document-level metadata of the PDF file
+/// - `fingerprint` contains the fingerprint of the PDF file for change detection
+/// - `index_writer` contains the writer of the Tantivy index for parsing and indexing
 /// 
 /// ## Returns
 /// - None
-pub fn file_indexing(file_path: &String, index_path: &String, track_fail_file: Option<&String>) {
-    // Read text in PDF file
-    let (pdf_page_nums, pdf_texts) = match read_pdf(file_path, track_fail_file) {
+pub fn parse_and_index_pdf(pdf_file: &str, contents: &PdfContents, fingerprint: &FileFingerprint, index_writer: &mut IndexWriter) -> Result<(), IndexingError> {
+    let index: Index = index_writer.index().clone();
+
+    // Snapshot of the index for detecting earlier indexing of the PDF file and its content
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+    let previous_fingerprint: Option<FileFingerprint> = read_indexed_fingerprint(pdf_file, &searcher)?;
+
+    add_file_location(index_writer, pdf_file, fingerprint)?;
+    if count_content_documents(&fingerprint.content_hash, DOC_KIND_PAGE, &searcher)? == 0 {
+        add_page_documents(index_writer, &contents.pdf_page_nums, &contents.pdf_texts, &contents.pdf_languages, &contents.document_info, &fingerprint.content_hash)?;
+    }
+
+    // Remove the pages of the previous content, unless other PDF files still share it
+    if let Some(s) = previous_fingerprint {
+        if s.content_hash != fingerprint.content_hash {
+            let pending_locations: HashMap<String, Option<String>> = HashMap::from([(pdf_file.to_string(), Some(fingerprint.content_hash.clone()))]);
+            delete_orphaned_contents(&[s.content_hash], &pending_locations, &index, index_writer)?;
+        }
+    }
+
+    // Commit changes to the index (deletions and additions become visible together)
+    match index_writer.commit() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(IndexingError::IndexDocumentCommitError(e))
+    }
+}
+
+/// Replaces the location document of a PDF file using an existing index writer (without committing)
+fn add_file_location(index_writer: &IndexWriter, pdf_file: &str, fingerprint: &FileFingerprint) -> Result<(), IndexingError> {
+    // Define the index fields for storing the file location
+    let schema = index_writer.index().schema();
+    let doc_kind_field = get_field(&schema, "doc_kind")?;
+    let path_field = get_field(&schema, "path")?;
+    let path_facet_field = get_field(&schema, "path_facet")?;
+    let file_size_field = get_field(&schema, "file_size")?;
+    let file_modified_field = get_field(&schema, "file_modified")?;
+    let content_hash_field = get_field(&schema, "content_hash")?;
+    let base_dir_field = get_field(&schema, "base_dir")?;
+
+    // Remove the location from any earlier indexing of the PDF file
+    index_writer.delete_term(Term::from_field_text(path_field, pdf_file));
+
+    let mut doc = Document::default();
+    doc.add_text(doc_kind_field, DOC_KIND_FILE);
+    doc.add_text(path_field, pdf_file);
+    doc.add_facet(path_facet_field, path_facet(pdf_file));
+    doc.add_u64(file_size_field, fingerprint.size);
+    doc.add_u64(file_modified_field, fingerprint.modified);
+    doc.add_text(content_hash_field, &fingerprint.content_hash);
+
+    // Relative paths can only be checked on disk against the directory they were indexed from
+    if std::path::Path::new(pdf_file).is_relative() {
+        doc.add_text(base_dir_field, indexing_base_dir());
+    }
+
+    match index_writer.add_document(doc) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(IndexingError::IndexDocumentAddError(e))
+    }
+}
+
+/// Adds the page documents of a unique PDF content using an existing index writer (without committing)
+/// 
+/// The text of a page goes to the content field of its detected language, if the index has one.
+fn add_page_documents(index_writer: &IndexWriter, pdf_page_num: &[u32], pdf_text: &[String], pdf_languages: &[Option<String>], document_info: &DocumentInfo, content_hash: &str) -> Result<(), IndexingError> {
+    // Define the index fields for storing page contents
+    let schema = index_writer.index().schema();
+    let doc_kind_field = get_field(&schema, "doc_kind")?;
+    let content_field = get_field(&schema, "content")?;
+    let page_num_field = get_field(&schema, "page_num")?;
+    let num_pages_field = get_field(&schema, "num_pages")?;
+    let content_hash_field = get_field(&schema, "content_hash")?;
+    let title_field = get_field(&schema, "title")?;
+    let author_field = get_field(&schema, "author")?;
+    let subject_field = get_field(&schema, "subject")?;
+    let keywords_field = get_field(&schema, "keywords")?;
+    let producer_field = get_field(&schema, "producer")?;
+    let creation_date_field = get_field(&schema, "creation_date")?;
+    let mod_date_field = get_field(&schema, "mod_date")?;
+    let language_field = get_field(&schema, "language")?;
+
+    let num_pages: u64 = pdf_page_num.len() as u64;
+
+    // Create one Tantivy document per PDF page
+    for (idx, (page_num, text)) in pdf_page_num.iter().zip(pdf_text.iter()).enumerate() {
+        let language: Option<&String> = pdf_languages.get(idx).and_then(|s| s.as_ref());
+
+        // Route the text to the content field of its language (only present if language detection is enabled)
+        let page_content_field: Field = match language.map(|s| schema.get_field(&content_field_name(s))) {
+            Some(Ok(s)) => s,
+            _ => content_field
+        };
+
+        let mut doc = Document::default();
+        doc.add_text(doc_kind_field, DOC_KIND_PAGE);
+        doc.add_u64(page_num_field, *page_num as u64);
+        doc.add_u64(num_pages_field, num_pages);
+        doc.add_text(content_hash_field, content_hash);
+        doc.add_text(page_content_field, text);
+        if let Some(s) = language {
+            doc.add_text(language_field, s);
+        }
+
+        // Add document-level metadata to every page, so it can be used for field-scoped queries
+        let metadata_texts = [
+            (title_field, &document_info.title),
+            (author_field, &document_info.author),
+            (subject_field, &document_info.subject),
+            (keywords_field, &document_info.keywords),
+            (producer_field, &document_info.producer),
+        ];
+        for (field, value) in metadata_texts {
+            if let Some(s) = value {
+                doc.add_text(field, s);
+            }
+        }
+
+        let metadata_dates = [
+            (creation_date_field, &document_info.creation_date),
+            (mod_date_field, &document_info.mod_date),
+        ];
+        for (field, value) in metadata_dates {
+            if let Some(s) = value {
+                doc.add_date(field, DateTime::from_timestamp_secs(s.timestamp()));
+            }
+        }
+
+        // Add the document to the index
+        match index_writer.add_document(doc) {
+            Ok(_) => {},
+            Err(e) => return Err(IndexingError::IndexDocumentAddError(e))
+        };
+    }
+
+    Ok(())
+}
+
+/// Checks a PDF file against its indexed fingerprint and extracts its contents if it changed
+/// 
+/// A file is considered unchanged if its size and modification time match the
+/// indexed fingerprint, or otherwise if its content hash does. Unchanged files,
+/// and files whose content is already indexed under another path, are never
+/// opened with lopdf. The language of every page is only detected if `detect_languages` is set.
+fn extract_file(file_path: &String, searcher: &Searcher, detect_languages: bool) -> Result<ExtractedFile, IndexingError> {
+    // Read size and modification time of the PDF file
+    let (size, modified) = match get_file_stats(file_path) {
         Ok(s) => s,
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            return;
+            Span::current().record("error_code", err.code());
+            return Ok(ExtractedFile::Failed);
         }
     };
 
-    info!(target:"info_logging", "{} - File read successfully.", &file_path);
-    trace!(target:"other_logging", "`{}` -> pdf_page_nums: {:?}", &file_path, &pdf_page_nums);
+    // Compare against the fingerprint captured in earlier indexing processes
+    let indexed_fingerprint: Option<FileFingerprint> = read_indexed_fingerprint(file_path, searcher)?;
+    trace!(target:"other_logging", "`{}` -> indexed fingerprint: {:?}", &file_path, &indexed_fingerprint);
 
-    // Create or open the Tantivy index
-    let index: tantivy::Index = match create_or_open_index(index_path) {
+    if let Some(s) = &indexed_fingerprint {
+        if s.size == size && s.modified == modified {
+            return Ok(ExtractedFile::Unchanged);
+        }
+    }
+
+    let content_hash: String = match compute_content_hash(file_path) {
         Ok(s) => s,
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            std::process::exit(1);
+            Span::current().record("error_code", err.code());
+            return Ok(ExtractedFile::Failed);
         }
     };
 
-    info!(target:"info_logging", "Index writer created successfully for `{}` directory.", &index_path);
+    let fingerprint: FileFingerprint = FileFingerprint { size, modified, content_hash };
 
-    // Parse PDF and index content
-    match parse_and_index_pdf(file_path, pdf_page_nums, pdf_texts, &index) {
-        Ok(_) => {},
+    // Record the new size and modification time, so that the file isn't hashed again by later runs
+    if let Some(s) = &indexed_fingerprint {
+        if s.content_hash == fingerprint.content_hash {
+            return Ok(ExtractedFile::Touched(fingerprint));
+        }
+    }
+
+    let previous_hash: Option<String> = indexed_fingerprint.map(|s| s.content_hash);
+
+    // Only record the location if an identical PDF file is indexed under another path
+    if count_content_documents(&fingerprint.content_hash, DOC_KIND_PAGE, searcher)? > 0 {
+        trace!(target:"other_logging", "`{}` -> content `{}` already indexed.", &file_path, &fingerprint.content_hash);
+        return Ok(ExtractedFile::Ready(Box::new(ExtractedPdf { fingerprint, previous_hash, contents: None })));
+    }
+
+    // Read text in PDF file
+    let contents: PdfContents = match read_pdf(file_path, detect_languages, None) {
+        Ok(s) => PdfContents::from(s),
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            std::process::exit(1);
+            Span::current().record("error_code", err.code());
+            return Ok(ExtractedFile::Failed);
         }
     };
-}
\ No newline at end of file
+
+    info!(target:"info_logging", "{} - File read successfully.", &file_path);
+    Span::current().record("pages", contents.pdf_page_nums.len());
+    trace!(target:"other_logging", "`{}` -> pdf_page_nums: {:?}", &file_path, &contents.pdf_page_nums);
+
+    Ok(ExtractedFile::Ready(Box::new(ExtractedPdf {
+        fingerprint,
+        previous_hash,
+        contents: Some(contents),
+    })))
+}
+
+/// Parse and index multiple PDF files, skipping files unchanged since the last run
+/// 
+/// Text is extracted by a pool of worker threads, which feed a single long-lived
+/// index writer. Changes are committed in batches, and segments are merged using
+/// a log merge policy once all files are indexed. PDF files sharing the same
+/// content hash are only indexed once, with every path recorded as a location.
+/// 
+/// ## Input Parameters
+/// - `files` contains the PDF files to be indexed
+/// - `index` contains the Tantivy index for parsing and indexing
+/// - `options` defines the worker pool, index writer and commit batch configuration
+/// - `track_fail_file` defines the file path for storing failed processing file names
+/// 
+/// ## Returns
+/// - Vector of PDF files with the outcome of their indexing operation
+pub fn index_files(files: &[String], index: &Index, options: &IndexingOptions, track_fail_file: Option<&String>) -> Result<Vec<(String, IndexingStatus)>, IndexingError> {
+    // Create the shared Tantivy index writer
+    let mut index_writer: IndexWriter = create_index_writer(index, options)?;
+
+    let statuses: Vec<(String, IndexingStatus)> = index_files_with_writer(files, index, &mut index_writer, options, track_fail_file)?;
+
+    // Wait for segment merges triggered by the merge policy to complete
+    match index_writer.wait_merging_threads() {
+        Ok(_) => {},
+        Err(e) => return Err(IndexingError::IndexSegmentMergeError(e))
+    };
+
+    Ok(statuses)
+}
+
+/// Creates the index writer used for indexing PDF files, merging segments with a log merge policy
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index to be written to
+/// - `options` defines the number of writer threads and their memory budget
+/// 
+/// ## Returns
+/// - Tantivy index writer
+pub fn create_index_writer(index: &Index, options: &IndexingOptions) -> Result<IndexWriter, IndexingError> {
+    let index_writer: IndexWriter = match index
+        .writer_with_num_threads(options.writer_threads.max(1), options.writer_memory_bytes) {
+            Ok(s) => s,
+            Err(e) => return Err(IndexingError::IndexWriterCreateError(e))
+        };
+    index_writer.set_merge_policy(Box::new(LogMergePolicy::default()));
+
+    Ok(index_writer)
+}
+
+/// Parse and index multiple PDF files using an existing index writer, as with [`index_files`]
+/// 
+/// Changes are committed before returning, while segment merges may continue in the
+/// background of the index writer.
+/// 
+/// ## Input Parameters
+/// - `files` contains the PDF files to be indexed
+/// - `index` contains the Tantivy index for parsing and indexing
+/// - `index_writer` contains the index writer of `index`
+/// - `options` defines the worker pool and commit batch configuration
+/// - `track_fail_file` defines the file path for storing failed processing file names
+/// 
+/// ## Returns
+/// - Vector of PDF files with the outcome of their indexing operation
+pub fn index_files_with_writer(files: &[String], index: &Index, index_writer: &mut IndexWriter, options: &IndexingOptions, track_fail_file: Option<&String>) -> Result<Vec<(String, IndexingStatus)>, IndexingError> {
+    // Record the indexing run as a span, with a child span per PDF file
+    let run_span: Span = info_span!("index_run", files = files.len(), indexed = Empty, unchanged = Empty, failed = Empty, duration_ms = Empty, error_code = Empty);
+    let started: Instant = Instant::now();
+
+    let result = run_span.in_scope(|| run_indexing(files, index, index_writer, options, track_fail_file, &run_span));
+    match &result {
+        Ok(s) => {
+            let count = |kind: &[IndexingStatus]| s.iter().filter(|(_, status)| kind.contains(status)).count();
+            run_span.record("indexed", count(&[IndexingStatus::Indexed, IndexingStatus::Reindexed, IndexingStatus::Duplicate]));
+            run_span.record("unchanged", count(&[IndexingStatus::Unchanged]));
+            run_span.record("failed", count(&[IndexingStatus::Failed]));
+        },
+        Err(err) => {
+            run_span.record("error_code", err.code());
+        }
+    };
+    run_span.record("duration_ms", started.elapsed().as_millis() as u64);
+
+    result
+}
+
+/// Runs the indexing of [`index_files_with_writer`] within the span of the indexing run
+fn run_indexing(files: &[String], index: &Index, index_writer: &mut IndexWriter, options: &IndexingOptions, track_fail_file: Option<&String>, run_span: &Span) -> Result<Vec<(String, IndexingStatus)>, IndexingError> {
+    // Snapshot of the index for comparing fingerprints
+    let searcher: Searcher = match index.reader() {
+        Ok(s) => s.searcher(),
+        Err(e) => return Err(IndexingError::IndexReaderCreateError(e))
+    };
+
+    // Page languages are only needed if the index was created with language detection,
+    // which adds one content field per supported language
+    let detect_languages: bool = SUPPORTED_LANGUAGES.iter().any(|s| searcher.schema().get_field(&content_field_name(s)).is_ok());
+
+    let num_workers: usize = options.num_workers.clamp(1, files.len().max(1));
+    debug!(target:"other_logging", "Indexing {} file(s) with {} worker(s) and {} writer thread(s).", files.len(), num_workers, options.writer_threads);
+
+    let next_file: AtomicUsize = AtomicUsize::new(0);
+    let mut statuses: Vec<(String, IndexingStatus)> = Vec::new();
+
+    // Contents whose pages were added during this run, and the uncommitted locations and
+    // contents possibly left without any file by the current batch
+    let mut added_contents: HashSet<String> = HashSet::new();
+    let mut batch_locations: HashMap<String, Option<String>> = HashMap::new();
+    let mut replaced_contents: Vec<String> = Vec::new();
+
+    std::thread::scope(|scope| -> Result<(), IndexingError> {
+        let (sender, receiver) = mpsc::sync_channel::<(usize, Result<ExtractedFile, IndexingError>, Span, Instant)>(num_workers * 2);
+
+        // Spawn the text extraction workers
+        for _ in 0..num_workers {
+            let sender = sender.clone();
+            let (next_file, searcher) = (&next_file, &searcher);
+            scope.spawn(move || {
+                loop {
+                    let idx: usize = next_file.fetch_add(1, Ordering::Relaxed);
+                    if idx >= files.len() {
+                        break;
+                    }
+                    let file_span: Span = info_span!(parent: run_span, "index_file", path = %files[idx], pages = Empty, status = Empty, duration_ms = Empty, error_code = Empty);
+                    let started: Instant = Instant::now();
+                    let extracted: Result<ExtractedFile, IndexingError> = file_span.in_scope(|| extract_file(&files[idx], searcher, detect_languages));
+                    if let Err(err) = &extracted {
+                        file_span.record("error_code", err.code());
+                    }
+
+                    // Stop once the receiving end has given up
+                    if sender.send((idx, extracted, file_span, started)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Feed extracted files to the shared index writer
+        let mut uncommitted_files: usize = 0;
+        for (idx, extracted, file_span, started) in receiver {
+            let file: &String = &files[idx];
+            let _entered = file_span.enter();
+
+            let status: IndexingStatus = match extracted? {
+                ExtractedFile::Unchanged => IndexingStatus::Unchanged,
+                ExtractedFile::Touched(fingerprint) => {
+                    add_file_location(index_writer, file, &fingerprint)?;
+                    batch_locations.insert(file.clone(), Some(fingerprint.content_hash));
+                    uncommitted_files += 1;
+                    IndexingStatus::Unchanged
+                },
+                ExtractedFile::Failed => {
+                    if let Some(s) = track_fail_file {
+                        if let Err(e) = write_to_file(s, file) {
+                            return Err(IndexingError::TrackFileWriteError(e));
+                        }
+                    }
+                    IndexingStatus::Failed
+                },
+                ExtractedFile::Ready(pdf) => {
+                    add_file_location(index_writer, file, &pdf.fingerprint)?;
+
+                    // Copies of the same content within this run are only added once
+                    let content_hash: &String = &pdf.fingerprint.content_hash;
+                    let is_duplicate: bool = match &pdf.contents {
+                        Some(s) if added_contents.insert(content_hash.clone()) => {
+                            add_page_documents(index_writer, &s.pdf_page_nums, &s.pdf_texts, &s.pdf_languages, &s.document_info, content_hash)?;
+                            false
+                        },
+                        _ => true
+                    };
+
+                    if let Some(s) = &pdf.previous_hash {
+                        replaced_contents.push(s.clone());
+                    }
+                    batch_locations.insert(file.clone(), Some(content_hash.clone()));
+                    uncommitted_files += 1;
+
+                    if is_duplicate {
+                        IndexingStatus::Duplicate
+                    }
+                    else if pdf.previous_hash.is_some() {
+                        IndexingStatus::Reindexed
+                    }
+                    else {
+                        IndexingStatus::Indexed
+                    }
+                }
+            };
+            file_span.record("status", format!("{:?}", status).as_str());
+            file_span.record("duration_ms", started.elapsed().as_millis() as u64);
+            statuses.push((file.clone(), status));
+
+            // Commit changes to the index once the batch is full
+            if uncommitted_files >= options.commit_batch_size.max(1) {
+                commit_batch(&mut replaced_contents, &mut batch_locations, index, index_writer)?;
+                debug!(target:"other_logging", "Committed batch of {} indexed file(s).", uncommitted_files);
+                uncommitted_files = 0;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    // Commit remaining changes to the index
+    commit_batch(&mut replaced_contents, &mut batch_locations, index, index_writer)?;
+
+    Ok(statuses)
+}
+
+/// Commits a batch of indexed files, together with the removal of the pages of contents which changed files no longer reference
+fn commit_batch(replaced_contents: &mut Vec<String>, batch_locations: &mut HashMap<String, Option<String>>, index: &Index, index_writer: &mut IndexWriter) -> Result<(), IndexingError> {
+    let num_purged: usize = delete_orphaned_contents(replaced_contents, batch_locations, index, index_writer)?;
+
+    match index_writer.commit() {
+        Ok(_) => {},
+        Err(e) => return Err(IndexingError::IndexDocumentCommitError(e))
+    };
+    if num_purged > 0 {
+        debug!(target:"other_logging", "Removed pages of {} unreferenced document content(s).", num_purged);
+    }
+
+    replaced_contents.clear();
+    batch_locations.clear();
+    Ok(())
+}
+
+/// Parse and index a single PDF file, skipping it if unchanged since the last run
+/// 
+/// ## Input Parameters
+/// - `file_path` defines the input path for single PDF file
+/// - `index_path` defines the input path for storing the indexed files
+/// - `track_fail_file` defines the file path for storing failed processing file names
+/// 
+/// ## Returns
+/// - Outcome of the indexing operation for the PDF file
+pub fn file_indexing(file_path: &String, index_path: &String, track_fail_file: Option<&String>) -> Result<IndexingStatus, IndexingError> {
+    // Create or open the Tantivy index
+    let index: tantivy::Index = create_or_open_index(index_path)?;
+
+    info!(target:"info_logging", "Index writer created successfully for `{}` directory.", &index_path);
+
+    let options: IndexingOptions = IndexingOptions { num_workers: 1, ..IndexingOptions::default() };
+
+    // Parse PDF and index content
+    let statuses: Vec<(String, IndexingStatus)> = index_files(std::slice::from_ref(file_path), &index, &options, track_fail_file)?;
+    Ok(statuses.first().map(|(_, status)| *status).unwrap_or(IndexingStatus::Failed))
+}
+
+#[pymethods]
+impl IndexingOptions {
+    #[new]
+    #[pyo3(signature = (num_workers=None, writer_threads=None, writer_memory_bytes=None, commit_batch_size=None, analyzer=None))]
+    /// Creates the indexing configuration, using defaults for values not provided
+    fn py_new(num_workers: Option<usize>, writer_threads: Option<usize>, writer_memory_bytes: Option<usize>, commit_batch_size: Option<usize>, analyzer: Option<AnalyzerOptions>) -> Self {
+        let default_options: IndexingOptions = IndexingOptions::default();
+
+        IndexingOptions {
+            num_workers: num_workers.unwrap_or(default_options.num_workers),
+            writer_threads: writer_threads.unwrap_or(default_options.writer_threads),
+            writer_memory_bytes: writer_memory_bytes.unwrap_or(default_options.writer_memory_bytes),
+            commit_batch_size: commit_batch_size.unwrap_or(default_options.commit_batch_size),
+            analyzer,
+        }
+    }
+}
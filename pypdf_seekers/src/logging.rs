@@ -2,9 +2,14 @@
 //! 
 //! - Defines the supporting function for setting Log4rs handle with associated configurations
 //! - Provides flexibility to define log file path and log verbosity level
+//! - Logging is opt-in: the library only emits `log` events, which are written by log4rs once
+//!   `set_logging` is called (embedding applications may install their own logger instead)
+//! - Indexing runs, indexed files and searches are recorded as `tracing` spans, which are written
+//!   as JSON lines (one object per closed span, with its fields) once `set_json_logging` is called
 
+use crate::error::LoggingError;
 use log::LevelFilter;
-use log4rs::append::console::ConsoleAppender;
+use log4rs::append::console::{ConsoleAppender, Target};
 use log4rs::append::rolling_file::policy::compound::CompoundPolicy;
 use log4rs::append::rolling_file::policy::compound::{
     roll::delete::DeleteRoller, trigger::size::SizeTrigger,
@@ -13,6 +18,13 @@ use log4rs::append::rolling_file::RollingFileAppender;
 use log4rs::encode::pattern::PatternEncoder;
 use log4rs::config::{Appender, Logger, Root};
 use log4rs::Config;
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{reload, Registry};
 
 
 // Log file parameters
@@ -20,80 +32,236 @@ const ROLLING_SIZE_LIMIT: u64 = u64::pow(1024, 3);
 const LOG_PATTERN_INFO: &str = "{h({d(%Y-%m-%d %H:%M:%S)(utc)} | {h({l}):5.5} | {m}{n})}";
 const LOG_PATTERN_OTHERS: &str = "{h({d(%Y-%m-%d %H:%M:%S)(utc)} | {h({l}):5.5} | {f}:{L} - {m}{n})}";
 
+/// Log4rs handle of the global logger installed by `set_logging` (if any)
+static LOG_HANDLE: OnceLock<Mutex<Option<log4rs::Handle>>> = OnceLock::new();
+
+/// JSON-lines log file written to by the tracing subscriber (spans are discarded if none)
+static JSON_LOG_FILE: OnceLock<Mutex<Option<File>>> = OnceLock::new();
+
+/// Reload handle for the verbosity level of the tracing subscriber installed by `set_json_logging` (if any)
+static JSON_LOG_LEVEL: OnceLock<reload::Handle<tracing_subscriber::filter::LevelFilter, Registry>> = OnceLock::new();
+
+
+/// Writer appending the formatted spans to the current JSON-lines log file
+struct JsonLogWriter;
+
+impl Write for JsonLogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut json_log_file = match JSON_LOG_FILE.get_or_init(|| Mutex::new(None)).lock() {
+            Ok(s) => s,
+            Err(e) => e.into_inner()
+        };
+        match json_log_file.as_mut() {
+            Some(s) => s.write(buf),
+            None => Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let mut json_log_file = match JSON_LOG_FILE.get_or_init(|| Mutex::new(None)).lock() {
+            Ok(s) => s,
+            Err(e) => e.into_inner()
+        };
+        match json_log_file.as_mut() {
+            Some(s) => s.flush(),
+            None => Ok(())
+        }
+    }
+}
+
+
+/// Parses the log verbosity level
+/// 
+/// ## Input parameters:
+/// - `log_level` defines the log verbosity level (INFO, WARN, DEBUG, ERROR, TRACE, OFF), case-insensitive
+/// 
+/// ## Returns:
+/// - Log level filter (INFO if no value is provided)
+pub fn parse_log_level(log_level: &Option<String>) -> Result<LevelFilter, LoggingError> {
+    match log_level {
+        Some(s) => match s.to_string().to_lowercase().as_str() {
+            "info" => Ok(LevelFilter::Info),
+            "warn" => Ok(LevelFilter::Warn),
+            "trace" => Ok(LevelFilter::Trace),
+            "debug" => Ok(LevelFilter::Debug),
+            "error" => Ok(LevelFilter::Error),
+            "off" => Ok(LevelFilter::Off),
+            _  => Err(LoggingError::InvalidLogLevel(s.to_string()))
+        },
+        None => Ok(LevelFilter::Info)
+    }
+}
 
 /// Sets the log4rs handle with associated configurations
 /// 
+/// The global logger is installed on the first call. Later calls in the same process replace
+/// its configuration (e.g. to change the log file or verbosity level) instead of failing.
+/// An error is returned if another logger was already installed by the embedding application.
+/// 
 /// ## Input parameters:
 /// - `log_file` defines the file path for creation of log files
 /// - `log_level` defines the log verbosity level
 /// 
 /// ## Returns:
 /// - Log4rs handle
-pub fn set_logging(log_file: &String, log_level: &Option<String>) -> Result<log4rs::Handle, Box<dyn std::error::Error>> {
+pub fn set_logging(log_file: &str, log_level: &Option<String>) -> Result<log4rs::Handle, LoggingError> {
     // Define the log verbosity level based on input parameter
-    let log_level: LevelFilter = match log_level {
-        Some(s) => match s.to_string().to_lowercase().as_str() {
-            "info" => LevelFilter::Info,
-            "warn" => LevelFilter::Warn,
-            "trace" => LevelFilter::Trace,
-            "debug" => LevelFilter::Debug,
-            "error" => LevelFilter::Error,
-            "off" => LevelFilter::Off,
-            _  => panic!("Invalid log verbosity level.")
-        },
-        None => LevelFilter::Info
-    };
+    let log_level: LevelFilter = parse_log_level(log_level)?;
 
     // Create rolling trigger for log files
     let trigger: Box<SizeTrigger> = Box::new(SizeTrigger::new(ROLLING_SIZE_LIMIT));
     let roller: Box<DeleteRoller> = Box::new(DeleteRoller::new());
     let compound_policy: Box<CompoundPolicy> = Box::new(CompoundPolicy::new(trigger, roller));
 
-    let file_logger_info: RollingFileAppender = RollingFileAppender::builder()
+    let file_logger_info: RollingFileAppender = match RollingFileAppender::builder()
         .encoder(Box::new(PatternEncoder::new(LOG_PATTERN_INFO)))
-        .build(log_file, compound_policy)?;
+        .build(log_file, compound_policy) {
+            Ok(s) => s,
+            Err(e) => return Err(LoggingError::LogFileCreateError(log_file.to_string(), e))
+        };
 
     let trigger: Box<SizeTrigger> = Box::new(SizeTrigger::new(ROLLING_SIZE_LIMIT));
     let roller: Box<DeleteRoller> = Box::new(DeleteRoller::new());
     let compound_policy: Box<CompoundPolicy> = Box::new(CompoundPolicy::new(trigger, roller));
 
-    let file_logger_others: RollingFileAppender = RollingFileAppender::builder()
+    let file_logger_others: RollingFileAppender = match RollingFileAppender::builder()
         .encoder(Box::new(PatternEncoder::new(LOG_PATTERN_OTHERS)))
-        .build(log_file, compound_policy)?;
+        .build(log_file, compound_policy) {
+            Ok(s) => s,
+            Err(e) => return Err(LoggingError::LogFileCreateError(log_file.to_string(), e))
+        };
     
-    // Create logger for writing to terminal
-    let stdout_info: ConsoleAppender = ConsoleAppender::builder()
+    // Create logger for writing to terminal (on stderr, to keep stdout free for command output)
+    let stderr_info: ConsoleAppender = ConsoleAppender::builder()
+        .target(Target::Stderr)
         .encoder(Box::new(PatternEncoder::new(LOG_PATTERN_INFO)))
         .build();
 
-    let stdout_others: ConsoleAppender = ConsoleAppender::builder()
+    let stderr_others: ConsoleAppender = ConsoleAppender::builder()
+        .target(Target::Stderr)
         .encoder(Box::new(PatternEncoder::new(LOG_PATTERN_OTHERS)))
         .build();
 
     // Create logging config for Log4rs
-    let config: Config = Config::builder()
-        .appender(Appender::builder().build("stdout_info", Box::new(stdout_info)))
-        .appender(Appender::builder().build("stdout_others", Box::new(stdout_others)))
+    let config: Config = match Config::builder()
+        .appender(Appender::builder().build("stderr_info", Box::new(stderr_info)))
+        .appender(Appender::builder().build("stderr_others", Box::new(stderr_others)))
         .appender(Appender::builder().build("file_logger_info", Box::new(file_logger_info)))
         .appender(Appender::builder().build("file_logger_others", Box::new(file_logger_others)))
         .logger(
             Logger::builder()
-                .appender("stdout_info")
+                .appender("stderr_info")
                 .appender("file_logger_info")
                 .build("info_logging", log_level),
         )
         .logger(
             Logger::builder()
-                .appender("stdout_others")
+                .appender("stderr_others")
                 .appender("file_logger_others")
                 .build("other_logging", log_level),
         )
         .build(
             Root::builder()
             .build(LevelFilter::Off)
-        )?;
+        ) {
+            Ok(s) => s,
+            Err(e) => return Err(LoggingError::LogConfigError(e))
+        };
+
+    // Reconfigure the installed logger, or create log4rs handle based on config defined
+    let mut log_handle = match LOG_HANDLE.get_or_init(|| Mutex::new(None)).lock() {
+        Ok(s) => s,
+        Err(e) => e.into_inner()
+    };
+    let handle: log4rs::Handle = match log_handle.as_ref() {
+        Some(s) => {
+            s.set_config(config);
+            s.clone()
+        },
+        None => match log4rs::init_config(config) {
+            Ok(s) => s,
+            Err(e) => return Err(LoggingError::LoggerAlreadySetError(e))
+        }
+    };
+    *log_handle = Some(handle.clone());
 
-    // Create log4rs handle based on config defined
-    let handle = log4rs::init_config(config)?;
     Ok(handle)
-}
\ No newline at end of file
+}
+
+/// Converts the log verbosity level to the equivalent tracing level filter
+fn to_tracing_level(log_level: LevelFilter) -> tracing_subscriber::filter::LevelFilter {
+    match log_level {
+        LevelFilter::Off => tracing_subscriber::filter::LevelFilter::OFF,
+        LevelFilter::Error => tracing_subscriber::filter::LevelFilter::ERROR,
+        LevelFilter::Warn => tracing_subscriber::filter::LevelFilter::WARN,
+        LevelFilter::Info => tracing_subscriber::filter::LevelFilter::INFO,
+        LevelFilter::Debug => tracing_subscriber::filter::LevelFilter::DEBUG,
+        LevelFilter::Trace => tracing_subscriber::filter::LevelFilter::TRACE,
+    }
+}
+
+/// Writes the spans of indexing runs, indexed files and searches to a JSON-lines log file
+/// 
+/// Every closed span is written as one JSON object, holding its fields (e.g. `path`, `pages`,
+/// `hits`, `duration_ms`, `error_code`) and those of its parent spans. The global tracing
+/// subscriber is installed on the first call. Later calls in the same process switch the log
+/// file and verbosity level instead of failing, and passing no log file stops writing spans.
+/// 
+/// ## Input parameters:
+/// - `json_log_file` defines the file path of the JSON-lines log file (spans are discarded if not provided)
+/// - `log_level` defines the log verbosity level
+pub fn set_json_logging(json_log_file: Option<&str>, log_level: &Option<String>) -> Result<(), LoggingError> {
+    // Define the log verbosity level based on input parameter
+    let log_level: tracing_subscriber::filter::LevelFilter = to_tracing_level(parse_log_level(log_level)?);
+
+    // Open the JSON-lines log file in append mode, creating its directory (if doesn't exist)
+    let file: Option<File> = match json_log_file {
+        Some(s) => {
+            if let Some(parent) = Path::new(s).parent() {
+                if let Err(e) = create_dir_all(parent) {
+                    return Err(LoggingError::JsonLogFileCreateError(s.to_string(), e));
+                }
+            }
+            match OpenOptions::new().create(true).append(true).open(s) {
+                Ok(f) => Some(f),
+                Err(e) => return Err(LoggingError::JsonLogFileCreateError(s.to_string(), e))
+            }
+        },
+        None => None
+    };
+
+    // Hold the log file while (re)configuring, so concurrent calls install a single subscriber
+    let mut current_file = match JSON_LOG_FILE.get_or_init(|| Mutex::new(None)).lock() {
+        Ok(s) => s,
+        Err(e) => e.into_inner()
+    };
+
+    // Update the verbosity level of the installed subscriber, or install the subscriber
+    match JSON_LOG_LEVEL.get() {
+        Some(s) => {
+            let _ = s.modify(|filter| *filter = log_level);
+        },
+        None => {
+            let (filter, handle) = reload::Layer::new(log_level);
+            let subscriber = Registry::default()
+                .with(filter)
+                .with(tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_span_list(true)
+                    .with_current_span(true)
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_writer(|| JsonLogWriter));
+
+            match tracing::subscriber::set_global_default(subscriber) {
+                Ok(_) => {},
+                Err(e) => return Err(LoggingError::SubscriberAlreadySetError(e))
+            };
+            let _ = JSON_LOG_LEVEL.set(handle);
+        }
+    };
+
+    // Switch the file the spans are written to
+    *current_file = file;
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! # Language Operations module
+//!
+//! - Detects the language of page texts offline (powered by whatlang)
+//! - Defines the languages whose pages are routed through a dedicated tokenizer and stemmer
+//! - Defines the bigram tokenizer used for segmenting Chinese and Japanese texts
+
+use tantivy::tokenizer::{Token, TokenStream, Tokenizer};
+use whatlang::Lang;
+
+/// Languages with a dedicated content field, tokenized by an analyzer for that language
+pub const SUPPORTED_LANGUAGES: [&str; 20] = [
+    "arabic", "danish", "dutch", "english", "finnish", "french", "german", "greek", "hungarian", "italian",
+    "norwegian", "portuguese", "romanian", "russian", "spanish", "swedish", "tamil", "turkish",
+    "chinese", "japanese",
+];
+
+/// Languages segmented into character bigrams instead of whitespace-separated words
+pub const CJK_LANGUAGES: [&str; 2] = ["chinese", "japanese"];
+
+/// Gets the language name used in the index for a detected language
+fn language_name(lang: Lang) -> String {
+    match lang {
+        Lang::Cmn => String::from("chinese"),
+        Lang::Nob => String::from("norwegian"),
+        _ => lang.eng_name().to_lowercase()
+    }
+}
+
+/// Detects the language of a text
+///
+/// ## Input Parameters
+/// - `text` contains the text of a single PDF page
+///
+/// ## Returns
+/// - Lowercase English name of the language (e.g. `german`), or None if it can't be detected reliably
+pub fn detect_language(text: &str) -> Option<String> {
+    match whatlang::detect(text) {
+        Some(s) if s.is_reliable() => Some(language_name(s.lang())),
+        _ => None
+    }
+}
+
+/// Gets the name of the content field holding pages written in a given language
+///
+/// ## Input Parameters
+/// - `language` defines the language name, e.g. `german`
+///
+/// ## Returns
+/// - Name of the language-specific content field, e.g. `content_german`
+pub fn content_field_name(language: &str) -> String {
+    format!("content_{}", language)
+}
+
+/// Checks if a language is segmented into character bigrams
+pub fn is_cjk_language(language: &str) -> bool {
+    CJK_LANGUAGES.contains(&language.trim().to_lowercase().as_str())
+}
+
+/// Checks if a character belongs to the Han, Hiragana or Katakana scripts
+fn is_cjk_char(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' |   // Hiragana and Katakana
+        '\u{31F0}'..='\u{31FF}' |   // Katakana phonetic extensions
+        '\u{3400}'..='\u{4DBF}' |   // CJK unified ideographs extension A
+        '\u{4E00}'..='\u{9FFF}' |   // CJK unified ideographs
+        '\u{F900}'..='\u{FAFF}' |   // CJK compatibility ideographs
+        '\u{FF66}'..='\u{FF9F}' |   // Halfwidth Katakana
+        '\u{20000}'..='\u{2FA1F}'   // CJK unified ideographs extensions B-F
+    )
+}
+
+#[derive(Clone, Default)]
+/// Tokenizer emitting overlapping character bigrams for runs of Chinese and Japanese characters
+///
+/// Runs of other alphanumeric characters are emitted as whole words, like the simple tokenizer.
+/// A run of a single CJK character is emitted as a unigram.
+pub struct CjkBigramTokenizer;
+
+/// Token stream of the CJK bigram tokenizer
+pub struct CjkBigramTokenStream {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl CjkBigramTokenizer {
+    /// Adds a token covering the given byte offsets
+    fn push_token(tokens: &mut Vec<Token>, text: &str, offset_from: usize, offset_to: usize) {
+        tokens.push(Token {
+            offset_from,
+            offset_to,
+            position: tokens.len(),
+            text: text[offset_from..offset_to].to_string(),
+            position_length: 1,
+        });
+    }
+
+    /// Splits a text into CJK bigrams and words
+    fn tokenize(text: &str) -> Vec<Token> {
+        let mut tokens: Vec<Token> = Vec::new();
+        let mut cjk_run: Vec<(usize, char)> = Vec::new();
+        let mut word_start: Option<usize> = None;
+
+        let flush_cjk = |tokens: &mut Vec<Token>, cjk_run: &mut Vec<(usize, char)>| {
+            if cjk_run.len() == 1 {
+                let (start, c) = cjk_run[0];
+                Self::push_token(tokens, text, start, start + c.len_utf8());
+            }
+            for pair in cjk_run.windows(2) {
+                let (start, _) = pair[0];
+                let (end, c) = pair[1];
+                Self::push_token(tokens, text, start, end + c.len_utf8());
+            }
+            cjk_run.clear();
+        };
+
+        for (offset, c) in text.char_indices() {
+            if is_cjk_char(c) {
+                if let Some(start) = word_start.take() {
+                    Self::push_token(&mut tokens, text, start, offset);
+                }
+                cjk_run.push((offset, c));
+            } else if c.is_alphanumeric() {
+                flush_cjk(&mut tokens, &mut cjk_run);
+                word_start.get_or_insert(offset);
+            } else {
+                flush_cjk(&mut tokens, &mut cjk_run);
+                if let Some(start) = word_start.take() {
+                    Self::push_token(&mut tokens, text, start, offset);
+                }
+            }
+        }
+
+        flush_cjk(&mut tokens, &mut cjk_run);
+        if let Some(start) = word_start {
+            Self::push_token(&mut tokens, text, start, text.len());
+        }
+
+        tokens
+    }
+}
+
+impl Tokenizer for CjkBigramTokenizer {
+    type TokenStream<'a> = CjkBigramTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        CjkBigramTokenStream { tokens: Self::tokenize(text), index: 0 }
+    }
+}
+
+impl TokenStream for CjkBigramTokenStream {
+    fn advance(&mut self) -> bool {
+        self.index += 1;
+        self.index <= self.tokens.len()
+    }
+
+    fn token(&self) -> &Token {
+        &self.tokens[self.index - 1]
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.tokens[self.index - 1]
+    }
+}
@@ -12,297 +12,331 @@
 pub mod error;
 pub mod logging;
 pub mod file_operations;
+pub mod analyzer_operations;
 pub mod index_operations;
+pub mod language_operations;
 pub mod search_operations;
+pub mod seeker;
 
 use file_operations::*;
 use index_operations::*;
 use search_operations::*;
 
+use error::PdfSeekerError;
+
 use chrono::prelude::Utc;
-use itertools::Itertools;
-use std::collections::HashMap;
 use log::{info, debug, error, trace};
 
 
-/// Create indexes for either single PDF file or directory containing multiple PDF files
+/// Logs the outcome of indexing a single PDF file
+fn log_indexing_status(file: &str, status: IndexingStatus) {
+    match status {
+        IndexingStatus::Indexed => info!(target:"info_logging", "{} - Indexing completed successfully.", file),
+        IndexingStatus::Reindexed => info!(target:"info_logging", "{} - File changed since last run. Re-indexing completed successfully.", file),
+        IndexingStatus::Unchanged => info!(target:"info_logging", "{} - Index information already captured.", file),
+        IndexingStatus::Duplicate => info!(target:"info_logging", "{} - Identical content already indexed. Location recorded.", file),
+        IndexingStatus::Failed => error!(target:"other_logging", "{} - Indexing failed.", file),
+    }
+}
+
+/// Logs the outcome of every indexed PDF file, followed by a summary of the indexing run
+pub(crate) fn log_indexing_summary(statuses: &[(String, IndexingStatus)], ignored_list: &[String]) {
+    for (file, status) in statuses {
+        log_indexing_status(file, *status);
+    }
+
+    let status_count = |status: IndexingStatus| statuses.iter().filter(|(_, s)| *s == status).count();
+    info!(target:"info_logging", "Indexing summary: {} indexed, {} re-indexed, {} unchanged, {} duplicate, {} failed, {} ignored.",
+        status_count(IndexingStatus::Indexed),
+        status_count(IndexingStatus::Reindexed),
+        status_count(IndexingStatus::Unchanged),
+        status_count(IndexingStatus::Duplicate),
+        status_count(IndexingStatus::Failed),
+        ignored_list.len()
+    );
+    for file in ignored_list {
+        info!(target:"info_logging", "{} - Ignored as per {} rules.", file, IGNORE_FILE_NAME);
+    }
+}
+
+/// Write the logs of all later operations to the console and to a rolling log file in the cache directory
+/// 
+/// Logging is opt-in: the library only emits `log` events and `tracing` spans, so applications
+/// with their own logger or subscriber don't need to call this. Calling it again in the same
+/// process replaces the log files and verbosity level of the loggers installed by the first call.
 /// 
 /// ## Input Parameters
-/// - `file_or_directory` defines the input path for single PDF file or directory containing multiple PDF files
 /// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
-/// - `log_level` defines the verbosity level for logs
-pub fn indexing_contents(file_or_directory: String, cache_path: Option<String>, log_level: Option<String>) -> Result<(), std::io::Error> {
+/// - `log_level` defines the verbosity level for logs (INFO if not provided)
+/// - `json_logs` defines whether the spans of indexing runs, indexed files and searches are also written to a JSON-lines log file
+pub fn setup_logging(cache_path: Option<String>, log_level: Option<String>, json_logs: bool) -> Result<(), PdfSeekerError> {
+    // Reject an invalid log verbosity level before creating any files
+    logging::parse_log_level(&log_level)?;
+
     // Create the directory for storing cache files (if doesn't exist)
-    let cache_dir: String = match create_cache_dir_if_not_exists(&cache_path) {
-        Ok(s) => s,
-        Err(err) => {
-            panic!("{}", err);
-        }
-    };
+    let cache_dir: String = create_cache_dir_if_not_exists(&cache_path)?;
+    let log_file: String = format!("{}/logs_dir/pdf_seekers_index-{}.log", &cache_dir, Utc::now().date_naive());
+
+    logging::set_logging(&log_file, &log_level)?;
+    debug!(target:"other_logging", "Logging to `{}` with verbosity level {:?}.", &log_file, &log_level);
+
+    // Write spans as JSON lines next to the text log file, or stop writing them
+    let json_log_file: Option<String> = json_logs.then(|| format!("{}/logs_dir/pdf_seekers_index-{}.jsonl", &cache_dir, Utc::now().date_naive()));
+    logging::set_json_logging(json_log_file.as_deref(), &log_level)?;
+    if let Some(s) = &json_log_file {
+        debug!(target:"other_logging", "Logging spans to `{}` as JSON lines.", s);
+    }
+
+    Ok(())
+}
+
+/// Create indexes for a set of PDF files, directories containing PDF files, or glob patterns
+/// 
+/// ## Input Parameters
+/// - `inputs` defines the input paths, each being a single PDF file, a directory containing multiple PDF files, or a glob pattern (e.g. `reports/2024-*.pdf`)
+/// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
+/// - `indexing_options` defines the worker pool, index writer, commit batch and new index analyzer configuration (defaults are used if not provided)
+/// - `traversal_options` defines how the directory tree is traversed (recursive without filters if not provided)
+pub fn indexing_contents(inputs: Vec<String>, cache_path: Option<String>, indexing_options: Option<IndexingOptions>, traversal_options: Option<TraversalOptions>) -> Result<(), PdfSeekerError> {
+    // Create the directory for storing cache files (if doesn't exist)
+    let cache_dir: String = create_cache_dir_if_not_exists(&cache_path)?;
 
-    // Define file paths for indexing, tracking and logging
+    // Define file paths for indexing and tracking
     let index_path: String = format!("{}/index_dir", &cache_dir);
     let track_path: String = format!("{}/track_dir", &cache_dir);
-    let track_success_file: String = format!("{}/_SUCCESS.txt", &track_path);
     let track_fail_file: String = format!("{}/_FAIL.txt", &track_path);
-    let log_file: String = format!("{}/logs_dir/pdf_seekers_index-{}.log", &cache_dir, Utc::now().date_naive());
 
     // Create the directory for storing tracker files (if doesn't exist)
     match create_track_dir_if_not_exists(track_path) {
         Ok(_) => {},
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            std::process::exit(1);
-        }
-    };
-
-    // Setup log4rs handle
-    let _log_handle = match logging::set_logging(&log_file, &log_level) {
-        Ok(s) => s,
-        Err(err) => {
-            panic!("{}", err);
+            return Err(err.into());
         }
     };
 
     info!(target:"info_logging", "Starting indexing operation...");
     debug!(target:"other_logging", "Input parameters:");
-    debug!(target:"other_logging", "file_or_directory: {}", &file_or_directory);
+    debug!(target:"other_logging", "inputs: {:?}", &inputs);
     debug!(target:"other_logging", "cache_path: {:?}", &cache_path);
-    debug!(target:"other_logging", "log_level: {:?}", &log_level);
-
-    // Check if indexing to be performed on single file or directory of files
-    let dir_flag: bool = check_if_directory(&file_or_directory);
-    trace!(target:"other_logging", "dir_flag: {}", &dir_flag);
+    debug!(target:"other_logging", "traversal_options: {:?}", &traversal_options);
 
-    if dir_flag {
-        info!(target:"info_logging", "Received `{}` which is directory.", &file_or_directory);
-    }
-    else {
-        info!(target:"info_logging", "Received `{}` which is file.", &file_or_directory);
-    }
+    let traversal_options: TraversalOptions = traversal_options.unwrap_or_default();
 
     match &cache_path {
         Some(s) => trace!(target:"other_logging", "Setting folder path `{}` as cache directory.", s),
         None => trace!(target:"other_logging", "No cache directory provided in input. Defaulting to `{}` directory.", &cache_dir)
     };
 
-    match &log_level {
-        Some(s) => trace!(target:"other_logging", "Setting log verbosity level to `{}`.", s.to_uppercase()),
-        None => trace!(target:"other_logging", "No log verbosity level provided in input. Defaulting to `INFO` log verbosity.")
-    }
 
-    if dir_flag {
-        // Create tracking files (if does not exists)
-        match std::path::Path::new(&track_success_file).exists() {
-            true => {},
-            false => {
-                write_to_file(&track_success_file, &String::from("")).unwrap();
-            }
-        }
+    write_to_file(&track_fail_file, &String::from(""))?;
 
-        write_to_file(&track_fail_file, &String::from("")).unwrap();
+    // Resolve the input files, directories and glob patterns into PDF files
+    let (files_list, ignored_list) = match resolve_input_paths(&inputs, &traversal_options, Some(&track_fail_file)) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
+        }
+    };
 
-        // Get all file names in directory
-        let files_list: Vec<String> = match get_files_in_directory(&file_or_directory, Some(&track_fail_file)) {
-            Ok(s) => s,
-            Err(err) => {
-                error!(target:"other_logging", "{}", err);
-                std::process::exit(1);
-            }
-        };
+    info!(target:"info_logging", "Resolved {} PDF file(s) from {} input path(s).", files_list.len(), inputs.len());
+    trace!(target:"other_logging", "File names resolved from {:?} -> {:?}", &inputs, &files_list);
 
-        info!(target:"info_logging", "Read all file names successfully in directory `{}`", &file_or_directory);
-        trace!(target:"other_logging", "File names read from `{}` directory -> {:?}", &file_or_directory, &files_list);
+    let indexing_options: IndexingOptions = indexing_options.unwrap_or_default();
+    debug!(target:"other_logging", "indexing_options: {:?}", &indexing_options);
 
-        // Get all processed file names (from prior indexing processes)
-        let processed_file: Vec<String> = match read_from_file(&track_success_file) {
-            Ok(s) => s,
-            Err(err) => {
-                error!(target:"other_logging", "{}", err);
-                std::process::exit(1);
-            }
-        };
+    // Create or open the Tantivy index (the analyzer is only applied to a new index)
+    let index: tantivy::Index = match create_or_open_index_with_analyzer(&index_path, indexing_options.analyzer.as_ref()) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
+        }
+    };
 
-        info!(target:"info_logging", "Read all processed file names successfully in `{}`", &track_success_file);
-        trace!(target:"other_logging", "Processed file names read from `{}` file -> {:?}", &track_success_file, &processed_file);
 
-        // Run indexing on new files in directory
-        for file in &files_list {
-            if !processed_file.contains(&file){
-                info!(target:"info_logging", "{} - Indexing started...", &file);
-                file_indexing(&file, &index_path, Some(&track_fail_file));
-                info!(target:"info_logging", "{} - Indexing completed successfully.", &file);
-                write_to_file(&track_success_file, &file).unwrap();
-            }
-            else {
-                info!(target:"info_logging", "{} - Index information already captured.", &file);
-            }
+    // Run indexing on new or changed files
+    info!(target:"info_logging", "Indexing started for {} file(s)...", files_list.len());
+    let statuses: Vec<(String, IndexingStatus)> = match index_files(&files_list, &index, &indexing_options, Some(&track_fail_file)) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
         }
+    };
 
-        // Get all errored out file names
-        let error_files: Vec<String> = match read_from_file(&track_fail_file) {
-            Ok(s) => s,
-            Err(err) => {
-                error!(target:"other_logging", "{}", err);
-                std::process::exit(1);
-            }
-        };
-        debug!("Errored out files during indexing process: {:?}", error_files);
-    }
-    else {
-        // Run indexing on single file
-        info!(target:"info_logging", "{} - Indexing started...", file_or_directory);
-        file_indexing(&file_or_directory, &index_path, Some(&track_fail_file));
-        info!(target:"info_logging", "{} - Indexing completed successfully.", &file_or_directory);
-    }
+    // Log the outcome of the indexing run
+    log_indexing_summary(&statuses, &ignored_list);
+
+    // Get all errored out file names
+    let error_files: Vec<String> = match read_from_file(&track_fail_file) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
+        }
+    };
+    debug!("Errored out files during indexing process: {:?}", error_files);
 
     Ok(())
 }
 
-/// Search for a keyword in either single PDF file or directory containing multiple PDF files
+/// Search for a keyword in a set of PDF files, directories containing PDF files, or glob patterns
+/// 
+/// Only hits from the indexed PDF files covered by `inputs` are returned (hits from all indexed PDF files if `inputs` is empty).
+/// 
+/// ## Input Parameters
+/// - `inputs` defines the input paths, each being a single PDF file, a directory containing multiple PDF files, or a glob pattern (e.g. `reports/2024-*.pdf`)
+/// - `search_term` defines the keyword to be searched in PDF documents
+/// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
+/// - `traversal_options` defines how the directory tree is traversed (recursive without filters if not provided)
+/// - `search_options` defines how the search term is matched, e.g. fuzzy matching, and which page of results is returned (10 best exact matches if not provided)
+pub fn search_term_in_file(inputs: Vec<String>, search_term: String, cache_path: Option<String>, traversal_options: Option<TraversalOptions>, search_options: Option<SearchOptions>) -> Result<Vec<PDFMetadata>, PdfSeekerError> {
+    search_term_in_file_with_total(inputs, search_term, cache_path, traversal_options, search_options).map(|s| s.documents)
+}
+
+/// Search for a keyword in a set of PDF files, directories containing PDF files, or glob patterns, along with the total number of matched pages
+/// 
+/// Only hits from the indexed PDF files covered by `inputs` (all indexed PDF files if empty) are returned, as the inputs are matched
+/// against the paths stored in the index (so PDF files don't need to be reachable on disk), with
+/// `.seekerignore` rules applying at indexing time. The `limit` and `offset` of `search_options`
+/// select the matched pages within the inputs to be returned, so that results can be paged through.
+/// The matched PDF files are ordered by relevance score, unless another `sort_by` key is requested,
+/// which only reorders the PDF files of the returned pages (pages are always selected by relevance).
 /// 
 /// ## Input Parameters
-/// - `file_or_directory` defines the input path for single PDF file or directory containing multiple PDF files
+/// - `inputs` defines the input paths, each being a single PDF file, a directory containing multiple PDF files, or a glob pattern (e.g. `reports/2024-*.pdf`)
 /// - `search_term` defines the keyword to be searched in PDF documents
 /// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
-/// - `log_level` defines the verbosity level for logs
-pub fn search_term_in_file(file_or_directory: String, search_term: String, cache_path: Option<String>, log_level: Option<String>) -> Result<Vec<PDFMetadata>, std::io::Error> {
+/// - `traversal_options` defines how the directory tree is traversed (recursive without filters if not provided)
+/// - `search_options` defines how the search term is matched, e.g. fuzzy matching, and which page of results is returned (10 best exact matches if not provided)
+/// 
+/// ## Returns
+/// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+pub fn search_term_in_file_with_total(inputs: Vec<String>, search_term: String, cache_path: Option<String>, traversal_options: Option<TraversalOptions>, search_options: Option<SearchOptions>) -> Result<PDFSearchResults, PdfSeekerError> {
+    search_query_in_file(inputs, SearchQuery::parse(&search_term), cache_path, traversal_options, search_options)
+}
+
+/// Search for a typed query in a set of PDF files, directories containing PDF files, or glob patterns, along with the total number of matched pages
+/// 
+/// Queries are built with [`QueryBuilder`] and [`SearchQuery`], e.g. for matching exact phrases or words
+/// close to each other, without writing Tantivy's query syntax. Results are returned as with [`search_term_in_file_with_total`].
+/// 
+/// ## Input Parameters
+/// - `inputs` defines the input paths, each being a single PDF file, a directory containing multiple PDF files, or a glob pattern (e.g. `reports/2024-*.pdf`)
+/// - `query` defines the query to be searched in PDF documents
+/// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
+/// - `traversal_options` defines how the directory tree is traversed (recursive without filters if not provided)
+/// - `search_options` defines how single words are matched, e.g. fuzzy matching, and which page of results is returned (10 best exact matches if not provided)
+/// 
+/// ## Returns
+/// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+pub fn search_query_in_file(inputs: Vec<String>, query: SearchQuery, cache_path: Option<String>, traversal_options: Option<TraversalOptions>, search_options: Option<SearchOptions>) -> Result<PDFSearchResults, PdfSeekerError> {
     // Create the directory for storing cache files (if doesn't exist)
-    let cache_dir: String = match create_cache_dir_if_not_exists(&cache_path) {
-        Ok(s) => s,
-        Err(err) => {
-            panic!("{}", err);
-        }
-    };
+    let cache_dir: String = create_cache_dir_if_not_exists(&cache_path)?;
 
-    // Define file paths for indexing and logging
+    // Define file path for indexing
     let index_path: String = format!("{}/index_dir", &cache_dir);
-    let log_file: String = format!("{}/logs_dir/pdf_seekers_index-{}.log", &cache_dir, Utc::now().date_naive());
 
-    // Setup log4rs handle
-    let _log_handle = match logging::set_logging(&log_file, &log_level) {
-        Ok(s) => s,
-        Err(err) => {
-            panic!("{}", err);
-        }
-    };
-    
+
     info!(target:"info_logging", "Starting searching operation...");
     debug!(target:"other_logging", "Input parameters:");
-    debug!(target:"other_logging", "file_or_directory: {}", &file_or_directory);
-    debug!(target:"other_logging", "search_term: {}", &search_term);
+    debug!(target:"other_logging", "inputs: {:?}", &inputs);
+    debug!(target:"other_logging", "query: {:?}", &query);
     debug!(target:"other_logging", "cache_path: {:?}", &cache_path);
-    debug!(target:"other_logging", "log_level: {:?}", &log_level);
+    debug!(target:"other_logging", "traversal_options: {:?}", &traversal_options);
+    debug!(target:"other_logging", "search_options: {:?}", &search_options);
 
-    // Check if search to be performed on single file or directory of files
-    let dir_flag: bool = check_if_directory(&file_or_directory);
-    trace!(target:"other_logging", "dir_flag: {}", &dir_flag);
-
-    if dir_flag {
-        info!(target:"info_logging", "Received `{}` which is directory.", &file_or_directory);
-    }
-    else {
-        info!(target:"info_logging", "Received `{}` which is file.", &file_or_directory);
-    }
+    let traversal_options: TraversalOptions = traversal_options.unwrap_or_default();
+    let search_options: SearchOptions = search_options.unwrap_or_default();
 
     match &cache_path {
         Some(s) => trace!(target:"other_logging", "Setting folder path `{}` as cache directory.", s),
         None => trace!(target:"other_logging", "No cache directory provided in input. Defaulting to `{}` directory.", &cache_dir)
     };
 
-    match &log_level {
-        Some(s) => trace!(target:"other_logging", "Setting log verbosity level to `{}`.", s.to_uppercase()),
-        None => trace!(target:"other_logging", "No log verbosity level provided in input. Defaulting to `INFO` log verbosity.")
-    }
     
     // Create or open the Tantivy index
     let index: tantivy::Index = match create_or_open_index(&index_path) {
         Ok(s) => s,
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            std::process::exit(1);
+            return Err(err.into());
         }
     };
 
-    info!(target:"info_logging", "Index writer created successfully for `{}`.", &index_path);
+    info!(target:"info_logging", "Index opened successfully for searching at `{}`.", &index_path);
+
+    // Scope the search to the indexed PDF files covered by the input files, directories and glob patterns
+    let mut path_scopes: Vec<PathScope> = Vec::new();
+    for input in &inputs {
+        match PathScope::new(input, &traversal_options) {
+            Ok(s) => path_scopes.push(s),
+            Err(err) => {
+                error!(target:"other_logging", "{}", err);
+                return Err(err.into());
+            }
+        };
+    }
 
-    // Search for a term in the indexed PDFs
-    let matched_docs: HashMap<String, Vec<String>> = match search_keyword(&index, &search_term) {
+    // Search for a term in the indexed PDFs (all of them if no input is provided)
+    let path_scopes: Option<&[PathScope]> = if inputs.is_empty() { None } else { Some(&path_scopes) };
+    let search_results: SearchResults = match search_query_in_scope(&index, &query, &search_options, path_scopes) {
         Ok(s) => {
-            info!(target:"info_logging", "Retrieved matched documents successfully for `{}` search term.", &search_term);
+            info!(target:"info_logging", "Retrieved {} of {} matched pages successfully for `{:?}` search query.", s.page_hits.len(), s.total_hits, &query);
             s
         },
         Err(err) => {
             error!(target:"other_logging", "{}", err);
-            std::process::exit(1);
+            return Err(err.into());
         }
     };
 
-    // Run analysis on PDF documents containing the search term
-    let mut metadata_vec: Vec<PDFMetadata> = Vec::new();
-    if dir_flag {
-        // Get all file names in directory
-        let files_list: Vec<String> = match get_files_in_directory(&file_or_directory, None) {
-            Ok(s) => s,
-            Err(err) => {
-                error!(target:"other_logging", "{}", err);
-                std::process::exit(1);
-            }
-        };
+    // Group the matched pages by PDF file and order the files by the requested sort key
+    Ok(collect_documents(search_results, search_options.sort_by))
+}
 
-        info!(target:"info_logging", "Read all file names successfully in directory `{}`", &file_or_directory);
-        trace!(target:"other_logging", "File names read from `{}` directory -> {:?}", &file_or_directory, &files_list);
-        
-        // Traverse the matched PDF documents (containing the search term) to display the metadata information
-        for doc_name in matched_docs.keys().sorted() {
-            if files_list.contains(doc_name) {
-                let page_num: Vec<String> = matched_docs.get(doc_name).cloned().unwrap();
-
-                // Extract metadata information from matched PDF file
-                let metadata: PDFMetadata = match run_analysis(doc_name, &page_num, &search_term) {
-                    Ok(s) => s,
-                    Err(err) => {
-                        error!("{}", err);
-                        std::process::exit(1);
-                    }
-                };
-
-                info!(target:"info_logging", "{}: Metadata extracted successfully.", &doc_name);                
-                trace!(target:"other_logging", "{}: {:?}", &doc_name, &metadata);
-                metadata_vec.push(metadata);
-            }
-        }
-    }
-    else {
-        let mut match_doc_flag: bool = false;
-
-        // Traverse the matched PDF documents (containing the search term) to display the metadata information
-        for doc_name in matched_docs.keys().sorted() {
-            if doc_name == &file_or_directory {
-                let page_num: Vec<String> = matched_docs.get(doc_name).cloned().unwrap();
-
-                // Extract metadata information from given PDF file
-                let metadata: PDFMetadata = match run_analysis(doc_name, &page_num, &search_term) {
-                    Ok(s) => s,
-                    Err(err) => {
-                        error!("{}", err);
-                        std::process::exit(1);
-                    }
-                };
-
-                info!(target:"info_logging", "{}: Metadata extracted successfully.", &doc_name);
-                trace!(target:"other_logging", "{}: {:?}", &doc_name, &metadata);
-
-                metadata_vec.push(metadata);
-                match_doc_flag = true;
-                break;
-            }
+/// Remove index entries of PDF files which were deleted or moved on disk
+/// 
+/// ## Input Parameters
+/// - `cache_path` defines the input path for storing the indexed files, log files, and tracker files
+/// 
+/// ## Returns
+/// - Vector of PDF file paths removed from the index
+pub fn sync_index_contents(cache_path: Option<String>) -> Result<Vec<String>, PdfSeekerError> {
+    // Create the directory for storing cache files (if doesn't exist)
+    let cache_dir: String = create_cache_dir_if_not_exists(&cache_path)?;
+
+    // Define file path for indexing
+    let index_path: String = format!("{}/index_dir", &cache_dir);
+
+    info!(target:"info_logging", "Starting sync operation...");
+    debug!(target:"other_logging", "Input parameters:");
+    debug!(target:"other_logging", "cache_path: {:?}", &cache_path);
+
+    // Create or open the Tantivy index
+    let index: tantivy::Index = match create_or_open_index(&index_path) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
         }
+    };
 
-        if match_doc_flag == false {
-            info!(target:"info_logging", "No matching documents found");
+    // Purge index documents of PDF files missing on disk
+    let removed_files: Vec<String> = match sync_index(&index) {
+        Ok(s) => s,
+        Err(err) => {
+            error!(target:"other_logging", "{}", err);
+            return Err(err.into());
         }
+    };
+
+    for file in &removed_files {
+        info!(target:"info_logging", "{} - File no longer exists on disk. Removed from index.", file);
     }
+    info!(target:"info_logging", "Sync completed. {} file(s) removed from index.", removed_files.len());
 
-    Ok(metadata_vec)
-}
\ No newline at end of file
+    Ok(removed_files)
+}
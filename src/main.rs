@@ -1,86 +1,429 @@
-use clap::Parser;
-use pdf_seekers::*;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::builder::NonEmptyStringValueParser;
+use clap::error::ErrorKind;
+use pdf_seekers::analyzer_operations::AnalyzerOptions;
+use pdf_seekers::error::PdfSeekerError;
+use pdf_seekers::file_operations::{read_path_list, TraversalOptions};
+use pdf_seekers::index_operations::{IndexStats, IndexingOptions};
+use pdf_seekers::search_operations::{SearchMode, SearchOptions, SearchQuery, SortBy};
+use pdf_seekers::seeker::{PdfSeeker, PdfSeekerBuilder};
 
-#[derive(Parser, Default, Debug)]
+/// Exit status of a failed file I/O operation, e.g. a missing input path
+const EXIT_FILE_ERROR: i32 = 3;
+/// Exit status of a failed index operation
+const EXIT_INDEX_ERROR: i32 = 4;
+/// Exit status of a failed search operation
+const EXIT_SEARCH_ERROR: i32 = 5;
+/// Exit status of a failed logging setup
+const EXIT_LOGGING_ERROR: i32 = 6;
+/// Exit status if the requested PDF file or page is not in the index
+const EXIT_NOT_FOUND: i32 = 1;
+
+#[derive(Parser, Debug)]
 #[clap(author="Tapas Das <dlaststark@gmail.com>", version, about)]
 /// Simple parser and information extractor from PDF documents based on keyword search functionality (powered by Rust)
 struct Arguments {
-    #[clap(short, long)]
-    /// Action to be performed [index, search]
-    action: String,
-
-    #[clap(short, long)]
-    /// Provide single PDF file to be searched, or directory path containing multiple PDF files
-    file_or_directory: String,
-
-    #[clap(short, long)]
-    /// Keyword to be searched in PDF files (only required when action=Searching)
-    search_term: Option<String>,
-    
-    #[clap(short, long)]
+    #[clap(subcommand)]
+    command: Command,
+
+    #[clap(short, long, global = true)]
     /// Directory path where all indexed files, log files, and tracker files will be stored
     /// If no value is provided or if value is set to None, then this will be created in current working directory
     cache_path: Option<String>,
 
-    #[clap(short, long)]
+    #[clap(short, long, global = true)]
     /// Flag to indicate the verbosity level for logs
     /// Default value is set to Info. Allowed values are INFO, WARN, DEBUG, ERROR, TRACE, OFF.
     log_level: Option<String>,
+
+    #[clap(long, global = true)]
+    /// Also write the spans of indexing runs, indexed files and searches to a JSON-lines log file in the cache directory
+    json_logs: bool,
+}
+
+#[derive(Subcommand, Debug)]
+/// Defines the operations performed on the index
+enum Command {
+    /// Index PDF files, directories containing PDF files, or glob patterns
+    Index(IndexArgs),
+    /// Search for a keyword in indexed PDF files
+    Search(SearchArgs),
+    /// Remove the indexed PDF files covered by PDF files, directories or glob patterns from the index
+    Remove(RemoveArgs),
+    /// List the indexed PDF files
+    List,
+    /// Show the number of indexed PDF files, unique contents, pages and index segments
+    Stats,
+    /// Remove index entries of PDF files which were deleted or moved on disk
+    Sync,
+    /// Show the stored text of a page of an indexed PDF file
+    Show(ShowArgs),
+}
+
+#[derive(Args, Debug)]
+/// Defines the input paths of the index and search commands
+struct InputArgs {
+    #[clap(short, long, num_args = 1.., value_parser = NonEmptyStringValueParser::new())]
+    /// Provide PDF files, directories containing multiple PDF files, or glob patterns (e.g. `reports/2024-*.pdf`) to be processed
+    /// Required for indexing. If no input path is provided for searching, then all indexed PDF files are searched.
+    file_or_directory: Vec<String>,
+
+    #[clap(long)]
+    /// File containing newline-separated input paths, in addition to `--file-or-directory` (use `-` to read from stdin)
+    from_file: Option<String>,
+}
+
+#[derive(Args, Debug)]
+/// Defines how the directories of the index and search commands are traversed
+struct TraversalArgs {
+    #[clap(long)]
+    /// Maximum number of subdirectory levels to descend into (0 only reads the given directory)
+    /// If no value is provided, then all subdirectories are traversed.
+    max_depth: Option<usize>,
+
+    #[clap(long)]
+    /// Follow symbolic links while traversing directories
+    follow_symlinks: bool,
+
+    #[clap(long)]
+    /// Glob pattern of PDF files to be included, relative to the given directory (can be repeated)
+    include: Vec<String>,
+
+    #[clap(long)]
+    /// Glob pattern of PDF files or directories to be excluded, relative to the given directory (can be repeated), e.g. `**/drafts/**`
+    exclude: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+/// Defines the flags of the index command
+struct IndexArgs {
+    #[clap(flatten)]
+    inputs: InputArgs,
+
+    #[clap(flatten)]
+    traversal: TraversalArgs,
+
+    #[clap(long)]
+    /// Number of worker threads extracting text from PDF files in parallel
+    /// Defaults to the number of available CPU cores.
+    num_workers: Option<usize>,
+
+    #[clap(long)]
+    /// Number of indexing threads used by the index writer
+    writer_threads: Option<usize>,
+
+    #[clap(long)]
+    /// Memory budget of the index writer in MiB, split across its threads
+    writer_memory_mb: Option<usize>,
+
+    #[clap(long)]
+    /// Number of indexed PDF files after which changes are committed to the index
+    commit_batch_size: Option<usize>,
+
+    #[clap(long)]
+    /// Language used for stemming and stop words, e.g. english, german, french (only used when creating a new index)
+    language: Option<String>,
+
+    #[clap(long)]
+    /// Reduce words to their stem, so that `networks` matches `network` (requires --language; only used when creating a new index)
+    stemming: bool,
+
+    #[clap(long)]
+    /// Remove the built-in stop words of the language (requires --language; only used when creating a new index)
+    stop_words: bool,
+
+    #[clap(long)]
+    /// Additional stop word to be removed (can be repeated; only used when creating a new index)
+    stop_word: Vec<String>,
+
+    #[clap(long)]
+    /// Fold accented characters to ASCII, so that `résumé` matches `resume` (only used when creating a new index)
+    ascii_folding: bool,
+
+    #[clap(long)]
+    /// Keep the case of words, making searches case-sensitive (only used when creating a new index)
+    case_sensitive: bool,
+
+    #[clap(long)]
+    /// Tokenize and stem every page according to its detected language (only used when creating a new index)
+    detect_language: bool,
+}
+
+#[derive(Args, Debug)]
+/// Defines the flags of the search command
+struct SearchArgs {
+    #[clap(short, long, value_parser = NonEmptyStringValueParser::new())]
+    /// Keyword to be searched in PDF files
+    search_term: String,
+
+    #[clap(flatten)]
+    inputs: InputArgs,
+
+    #[clap(flatten)]
+    traversal: TraversalArgs,
+
+    #[clap(long, conflicts_with_all = ["wildcard", "regex"])]
+    /// Tolerate typos in the search term, e.g. `convolutonal` matches `convolutional`
+    fuzzy: bool,
+
+    #[clap(long, conflicts_with = "regex")]
+    /// Treat the search term as wildcard patterns, e.g. `regulat*` or `colo?r`
+    wildcard: bool,
+
+    #[clap(long)]
+    /// Treat the search term as a regular expression matching whole words, e.g. `regulat(ion|ory)`
+    regex: bool,
+
+    #[clap(long, requires = "fuzzy")]
+    /// Maximum number of edits (1 or 2) between the search term and a matched word
+    fuzzy_distance: Option<u8>,
+
+    #[clap(long)]
+    /// Maximum number of matched pages to be returned
+    /// Default value is set to 10. Together with the offset, at most 10000.
+    limit: Option<usize>,
+
+    #[clap(long)]
+    /// Number of best matched pages to be skipped, for paging through the results
+    offset: Option<usize>,
+
+    #[clap(long, value_parser = parse_sort_by)]
+    /// Order of the matched PDF files, applied to the pages selected by relevance (--limit and --offset)
+    /// Default value is set to relevance. Allowed values are relevance, path, mod-date, page-count.
+    sort_by: Option<SortBy>,
+}
+
+#[derive(Args, Debug)]
+/// Defines the flags of the remove command
+struct RemoveArgs {
+    #[clap(required = true, value_parser = NonEmptyStringValueParser::new())]
+    /// Indexed PDF files, directories or glob patterns to be removed (the files don't need to exist on disk)
+    paths: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+/// Defines the flags of the show command
+struct ShowArgs {
+    #[clap(value_parser = NonEmptyStringValueParser::new())]
+    /// Indexed PDF file path, as listed by the list command
+    file: String,
+
+    #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    /// Page number to be shown (starting at 1)
+    page: u32,
+}
+
+fn parse_sort_by(s: &str) -> Result<SortBy, String> {
+    s.parse::<SortBy>().map_err(|err| err.to_string())
 }
 
-fn validate_arguments(args: &Arguments) -> String {
-    if args.action.trim().len() == 0 {
-        panic!("action cannot contain empty values");
+fn traversal_options(args: &TraversalArgs) -> TraversalOptions {
+    TraversalOptions {
+        max_depth: args.max_depth,
+        follow_symlinks: args.follow_symlinks,
+        include: args.include.clone(),
+        exclude: args.exclude.clone(),
     }
+}
 
-    if args.file_or_directory.trim().len() == 0 {
-        panic!("file_or_directory cannot contain empty values");
+fn analyzer_options(args: &IndexArgs) -> Option<AnalyzerOptions> {
+    // Leave the analyzer choice to the index if no analyzer flag is provided
+    if args.language.is_none() && !args.stemming && !args.stop_words && args.stop_word.is_empty() && !args.ascii_folding && !args.case_sensitive && !args.detect_language {
+        return None;
     }
 
-    let mut search_term: String = String::from("");
-    if args.action.trim().to_lowercase() == "search" {
-        search_term = match args.search_term.as_ref() {
-            Some(s) => s.to_string(),
-            None => {
-                panic!("search_term is not provided for 'search' action");
-            }
-        };
+    Some(AnalyzerOptions {
+        language: args.language.clone(),
+        stemming: args.stemming,
+        stop_words: args.stop_words,
+        custom_stop_words: args.stop_word.clone(),
+        ascii_folding: args.ascii_folding,
+        lowercase: !args.case_sensitive,
+        detect_language: args.detect_language,
+    })
+}
 
-        if search_term.trim().len() == 0 {
-            panic!("search_term cannot contain empty values for 'search' action");
-        }
+fn indexing_options(args: &IndexArgs) -> IndexingOptions {
+    let default_options: IndexingOptions = IndexingOptions::default();
+
+    IndexingOptions {
+        num_workers: args.num_workers.unwrap_or(default_options.num_workers),
+        writer_threads: args.writer_threads.unwrap_or(default_options.writer_threads),
+        writer_memory_bytes: args.writer_memory_mb.map(|s| s << 20).unwrap_or(default_options.writer_memory_bytes),
+        commit_batch_size: args.commit_batch_size.unwrap_or(default_options.commit_batch_size),
+        analyzer: analyzer_options(args),
     }
+}
+
+fn search_options(args: &SearchArgs) -> SearchOptions {
+    let default_options: SearchOptions = SearchOptions::default();
 
-    search_term
+    SearchOptions {
+        mode: if args.fuzzy {
+            SearchMode::Fuzzy
+        } else if args.wildcard {
+            SearchMode::Wildcard
+        } else if args.regex {
+            SearchMode::Regex
+        } else {
+            SearchMode::Exact
+        },
+        fuzzy_distance: args.fuzzy_distance.unwrap_or(default_options.fuzzy_distance),
+        limit: args.limit.unwrap_or(default_options.limit),
+        offset: args.offset.unwrap_or(default_options.offset),
+        sort_by: args.sort_by.unwrap_or(default_options.sort_by),
+    }
 }
 
-fn main() {
-    // Read command line arguments
-    let args: Arguments = Arguments::parse();
+/// Reads the input paths given on the command line and in the `--from-file` list, exiting with a usage error if `required` and none are given
+fn read_inputs(args: &InputArgs, required: bool) -> Result<Vec<String>, PdfSeekerError> {
+    let mut inputs: Vec<String> = args.file_or_directory.clone();
 
-    // Validate command line arguments
-    let search_term: String = validate_arguments(&args);
+    if let Some(s) = args.from_file.as_ref() {
+        inputs.extend(read_path_list(s)?);
+    }
+
+    if required && inputs.is_empty() {
+        Arguments::command()
+            .error(ErrorKind::MissingRequiredArgument, "no input paths provided with --file-or-directory or --from-file")
+            .exit();
+    }
 
-    // Indexing the PDF files
-    if &args.action == "index" {
-        indexing_contents(
-            args.file_or_directory.clone(), 
-            args.cache_path.clone(), 
-            args.log_level.clone()
-        ).unwrap();
+    Ok(inputs)
+}
+
+/// Opens the index of the cache directory as a handle, writing logs to the console and to the cache directory
+fn open_seeker(args: &Arguments, builder: PdfSeekerBuilder) -> Result<PdfSeeker, PdfSeekerError> {
+    let mut builder: PdfSeekerBuilder = builder.logging(args.log_level.clone(), args.json_logs);
+    if let Some(s) = &args.cache_path {
+        builder = builder.cache_dir(s);
     }
 
-    // Search for provided keyword
-    if &args.action == "search" {
-        let metadata_vec = search_term_in_file(
-            args.file_or_directory, 
-            search_term, 
-            args.cache_path, 
-            args.log_level
-        ).unwrap();
-
-        for element in metadata_vec {
-            element.show();
+    builder.build()
+}
+
+/// Gets the exit status of a failed operation
+fn exit_code(err: &PdfSeekerError) -> i32 {
+    match err {
+        PdfSeekerError::FileOperations(_) => EXIT_FILE_ERROR,
+        PdfSeekerError::Indexing(_) => EXIT_INDEX_ERROR,
+        PdfSeekerError::Searching(_) => EXIT_SEARCH_ERROR,
+        PdfSeekerError::Logging(_) => EXIT_LOGGING_ERROR,
+    }
+}
+
+/// Prints the error of a failed operation and exits with the status of its error type
+fn exit_on_error<T>(result: Result<T, PdfSeekerError>) -> T {
+    match result {
+        Ok(s) => s,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(exit_code(&err));
         }
     }
-}
\ No newline at end of file
+}
+
+fn main() {
+    // Read and validate command line arguments
+    let args: Arguments = Arguments::parse();
+
+    // Every command runs on a handle of the index, which also sets up logging
+    match &args.command {
+        // Indexing the PDF files
+        Command::Index(index_args) => {
+            let inputs: Vec<String> = exit_on_error(read_inputs(&index_args.inputs, true));
+            let mut seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()
+                .indexing_options(indexing_options(index_args))
+                .traversal_options(traversal_options(&index_args.traversal))
+            ));
+            exit_on_error(seeker.index_paths(&inputs));
+            exit_on_error(seeker.close());
+        },
+
+        // Search for provided keyword
+        Command::Search(search_args) => {
+            let inputs: Vec<String> = exit_on_error(read_inputs(&search_args.inputs, false));
+            let seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()
+                .traversal_options(traversal_options(&search_args.traversal))
+            ));
+            let search_options: SearchOptions = search_options(search_args);
+            let search_results = exit_on_error(seeker.search_query(
+                &SearchQuery::parse(&search_args.search_term),
+                &inputs,
+                &search_options
+            ));
+
+            for element in &search_results.documents {
+                element.show();
+            }
+
+            // Show which matched pages were returned, so that the next ones can be requested with --offset
+            let shown_to: usize = std::cmp::min(search_options.offset + search_options.limit, search_results.total_hits);
+            println!("==================================================");
+            if search_options.offset < shown_to {
+                println!("Matched pages {}-{} of {}", search_options.offset + 1, shown_to, search_results.total_hits);
+            } else {
+                println!("No matched pages beyond offset {} ({} in total)", search_options.offset, search_results.total_hits);
+            }
+        },
+
+        // Remove PDF files from the index
+        Command::Remove(remove_args) => {
+            let mut seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()));
+            let mut removed_files: Vec<String> = Vec::new();
+            for path in &remove_args.paths {
+                removed_files.extend(exit_on_error(seeker.remove(path)));
+            }
+            exit_on_error(seeker.close());
+
+            for file in &removed_files {
+                println!("Removed from index: {}", file);
+            }
+            if removed_files.is_empty() {
+                eprintln!("No indexed PDF files found for {:?}", &remove_args.paths);
+                std::process::exit(EXIT_NOT_FOUND);
+            }
+        },
+
+        // List the indexed PDF files
+        Command::List => {
+            let seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()));
+            for file in exit_on_error(seeker.indexed_files()) {
+                println!("{}", file);
+            }
+        },
+
+        // Show the size of the index
+        Command::Stats => {
+            let seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()));
+            let stats: IndexStats = exit_on_error(seeker.stats());
+            println!("Indexed files: {}", stats.num_files);
+            println!("Unique contents: {}", stats.num_contents);
+            println!("Indexed pages: {}", stats.num_pages);
+            println!("Index segments: {}", stats.num_segments);
+        },
+
+        // Purge index entries of deleted PDF files
+        Command::Sync => {
+            let mut seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()));
+            let removed_files: Vec<String> = exit_on_error(seeker.sync());
+            exit_on_error(seeker.close());
+
+            for file in removed_files {
+                println!("Removed from index: {}", file);
+            }
+        },
+
+        // Show the stored text of an indexed page
+        Command::Show(show_args) => {
+            let seeker: PdfSeeker = exit_on_error(open_seeker(&args, PdfSeeker::builder()));
+            match exit_on_error(seeker.page_text(&show_args.file, show_args.page)) {
+                Some(s) => println!("{}", s),
+                None => {
+                    eprintln!("Page {} of `{}` is not in the index", show_args.page, &show_args.file);
+                    std::process::exit(EXIT_NOT_FOUND);
+                }
+            };
+        },
+    };
+}
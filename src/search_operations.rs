@@ -2,51 +2,1102 @@
 //! 
 //! - Defines the metadata structure to be captured
 //! - Defines the supporting functions for performing keyword search on PDF files
+//! - Defines the search modes (exact, typo-tolerant fuzzy, wildcard and regular expression matching)
+//! - Defines the typed query builder (boolean clauses, phrases, proximity, field scopes and boosts)
 //! - Defines the supporting functions for capturing metadata information from matched PDF files
+//! - Generates highlighted snippets of matched pages from the stored page contents (powered by Tantivy's snippet generator)
 
-use crate::error::{FileOperationsError, SearchingError};
-use log::{debug, trace};
-use lopdf::Document as lopdoc;
-use std::collections::HashMap;
-use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
+use crate::error::SearchingError;
+use crate::file_operations::{DocumentInfo, PathScope};
+use crate::index_operations::{path_facet, DOC_KIND_FILE, DOC_KIND_PAGE};
+use crate::language_operations::{content_field_name, SUPPORTED_LANGUAGES};
+use chrono::{DateTime, TimeZone, Utc};
+use log::{debug, info, trace, warn};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::str::FromStr;
+use std::time::Instant;
+use tantivy::{Document, Searcher, SnippetGenerator, Term};
+use tantivy::collector::{Count, DocSetCollector, TopDocs};
+use tantivy::query::{BooleanQuery, BoostQuery, ConstScoreQuery, FuzzyTermQuery, Occur, PhraseQuery, Query, QueryParser, RegexQuery, TermQuery, TermSetQuery};
+use tantivy::schema::{Field, FieldType, IndexRecordOption, Schema};
+use tantivy::tokenizer::{TextAnalyzer, Token, TokenStream};
+use tracing::{info_span, Span};
+use tracing::field::Empty;
 
-#[derive(Debug)]
+/// Highest Levenshtein distance supported by Tantivy's fuzzy term queries
+pub const MAX_FUZZY_DISTANCE: u8 = 2;
+
+/// Number of matched pages returned by a search, unless configured otherwise
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+/// Highest `limit + offset` of a search, as the collector allocates room for that many pages upfront
+pub const MAX_SEARCH_WINDOW: usize = 10_000;
+
+/// Maximum number of characters in the snippet of a matched page
+const SNIPPET_MAX_CHARS: usize = 200;
+
+/// Terminal escape codes for colouring highlighted words (bold red)
+const HIGHLIGHT_COLOR_START: &str = "\x1b[1;31m";
+const HIGHLIGHT_COLOR_END: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Defines how search terms are matched against the indexed words
+pub enum SearchMode {
+    /// Match the search terms as they are (after tokenization)
+    #[default]
+    Exact,
+    /// Match words within `fuzzy_distance` edits of the search terms, so that `convolutonal` matches `convolutional`
+    Fuzzy,
+    /// Match words against whitespace-separated wildcard patterns, where `*` stands for any characters
+    /// and `?` for a single character, e.g. `regulat*` or `colo?r` (query syntax is rejected)
+    Wildcard,
+    /// Match words against a regular expression covering the whole word, e.g. `regulat(ion|ory)`
+    /// (query syntax is rejected)
+    Regex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Defines the order of the matched PDF files in search results
+/// 
+/// The matched pages are always selected by relevance (the `limit` best pages after `offset`),
+/// and the other sort keys only reorder the PDF files of those pages. For example, sorting by
+/// path doesn't return the first PDF files by path among all matches.
+pub enum SortBy {
+    /// Most relevant PDF files first (highest page score)
+    #[default]
+    Relevance,
+    /// PDF files in alphabetical order of their path
+    Path,
+    /// Most recently modified PDF files first (by the modification date of the PDF document)
+    ModificationDate,
+    /// PDF files with the most pages first
+    PageCount,
+}
+
+impl FromStr for SortBy {
+    type Err = SearchingError;
+
+    /// Parses a sort key name (`relevance`, `path`, `mod-date` or `page-count`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "relevance" | "score" => Ok(SortBy::Relevance),
+            "path" => Ok(SortBy::Path),
+            "mod-date" | "modification-date" => Ok(SortBy::ModificationDate),
+            "page-count" | "pages" => Ok(SortBy::PageCount),
+            _ => Err(SearchingError::InvalidSearchOptions(format!("unsupported sort key `{}` (expected relevance, path, mod-date or page-count)", s)))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Defines the configuration of a keyword search
+pub struct SearchOptions {
+    /// How search terms are matched against the indexed words
+    pub mode: SearchMode,
+    /// Maximum Levenshtein distance (1 or 2) between a search term and a matched word (only used in fuzzy mode)
+    pub fuzzy_distance: u8,
+    /// Maximum number of matched pages to be returned (at least 1)
+    pub limit: usize,
+    /// Number of best matched pages to be skipped, for paging through the results
+    pub offset: usize,
+    /// Order of the matched PDF files in search results (applied to the pages selected by relevance)
+    pub sort_by: SortBy,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            mode: SearchMode::Exact,
+            fuzzy_distance: 1,
+            limit: DEFAULT_SEARCH_LIMIT,
+            offset: 0,
+            sort_by: SortBy::Relevance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Defines a typed search query, built without Tantivy's query syntax
+/// 
+/// Leaf queries search the page contents unless they are scoped to a metadata field with
+/// [`SearchQuery::in_field`], and their words are tokenized with the analyzer of that field,
+/// e.g. `QueryBuilder::new().must(SearchQuery::phrase("region proposal")).must_not(SearchQuery::term("smith").in_field("author")).build()`.
+pub enum SearchQuery {
+    /// Query string in Tantivy's query syntax (e.g. `network AND author:girshick`), matched according to the search mode
+    Raw(String),
+    /// Single word, matched according to the search mode (exact, fuzzy, wildcard or regex)
+    Term {
+        /// Word to be matched
+        word: String,
+        /// Field to be searched (page contents if None)
+        field: Option<String>,
+    },
+    /// Exact sequence of words, or nearly exact if `slop` is set
+    Phrase {
+        /// Words to be matched in order
+        words: String,
+        /// Field to be searched (page contents if None)
+        field: Option<String>,
+        /// Number of positions the words may be moved by, e.g. for other words in between (swapping two words takes 2)
+        slop: u32,
+    },
+    /// Two words at most `distance` words apart, in either order
+    /// 
+    /// Each operand must be a single word once tokenized, else the search fails with `InvalidSearchOptions`.
+    Proximity {
+        /// First word to be matched
+        first: String,
+        /// Second word to be matched
+        second: String,
+        /// Maximum number of words in between
+        distance: u32,
+        /// Field to be searched (page contents if None)
+        field: Option<String>,
+    },
+    /// Combination of queries, each one being required (`Must`), optional (`Should`) or excluded (`MustNot`)
+    /// 
+    /// A combination made of `MustNot` clauses only doesn't match anything.
+    Boolean(Vec<(Occur, SearchQuery)>),
+    /// Query whose relevance score is multiplied by a factor
+    Boost(Box<SearchQuery>, f32),
+}
+
+impl SearchQuery {
+    /// Creates a query from a string in Tantivy's query syntax
+    pub fn parse(query_str: &str) -> Self {
+        SearchQuery::Raw(query_str.to_string())
+    }
+
+    /// Creates a query matching a single word
+    pub fn term(word: &str) -> Self {
+        SearchQuery::Term { word: word.to_string(), field: None }
+    }
+
+    /// Creates a query matching an exact sequence of words
+    pub fn phrase(words: &str) -> Self {
+        SearchQuery::Phrase { words: words.to_string(), field: None, slop: 0 }
+    }
+
+    /// Creates a query matching two single words at most `distance` words apart, in either order
+    pub fn near(first: &str, second: &str, distance: u32) -> Self {
+        SearchQuery::Proximity { first: first.to_string(), second: second.to_string(), distance, field: None }
+    }
+
+    /// Scopes the query to a text field, e.g. `title` or `author` (raw queries are left untouched)
+    pub fn in_field(self, field_name: &str) -> Self {
+        match self {
+            SearchQuery::Term { word, .. } => SearchQuery::Term { word, field: Some(field_name.to_string()) },
+            SearchQuery::Phrase { words, slop, .. } => SearchQuery::Phrase { words, field: Some(field_name.to_string()), slop },
+            SearchQuery::Proximity { first, second, distance, .. } => SearchQuery::Proximity { first, second, distance, field: Some(field_name.to_string()) },
+            SearchQuery::Boolean(clauses) => SearchQuery::Boolean(clauses
+                .into_iter()
+                .map(|(occur, query)| (occur, query.in_field(field_name)))
+                .collect()),
+            SearchQuery::Boost(query, boost) => SearchQuery::Boost(Box::new(query.in_field(field_name)), boost),
+            SearchQuery::Raw(s) => SearchQuery::Raw(s)
+        }
+    }
+
+    /// Allows the words of a phrase to be moved by up to `slop` positions (other queries are left untouched)
+    pub fn with_slop(self, slop: u32) -> Self {
+        match self {
+            SearchQuery::Phrase { words, field, .. } => SearchQuery::Phrase { words, field, slop },
+            query => query
+        }
+    }
+
+    /// Multiplies the relevance score of the query by a factor
+    pub fn boost(self, boost: f32) -> Self {
+        SearchQuery::Boost(Box::new(self), boost)
+    }
+
+    /// Gets the words of the query searched in page contents (skipping excluded clauses), for finding their occurrences
+    fn content_keywords(&self, search_mode: SearchMode, keywords: &mut Vec<String>) {
+        let in_content = |field: &Option<String>| matches!(field.as_deref(), None | Some("content"));
+        // Words of phrases are matched literally, even in wildcard and regex modes
+        let literal = |words: &str| if search_mode == SearchMode::Regex {
+            words.split_whitespace().map(regex::escape).collect::<Vec<String>>().join("|")
+        } else {
+            words.to_string()
+        };
+
+        match self {
+            SearchQuery::Raw(s) => keywords.push(s.clone()),
+            SearchQuery::Term { word, field } if in_content(field) => keywords.push(word.clone()),
+            SearchQuery::Phrase { words, field, .. } if in_content(field) => keywords.push(literal(words)),
+            SearchQuery::Proximity { first, second, field, .. } if in_content(field) => keywords.extend([literal(first), literal(second)]),
+            SearchQuery::Boolean(clauses) => {
+                for (occur, query) in clauses {
+                    if *occur != Occur::MustNot {
+                        query.content_keywords(search_mode, keywords);
+                    }
+                }
+            },
+            SearchQuery::Boost(query, _) => query.content_keywords(search_mode, keywords),
+            _ => {}
+        }
+    }
+
+    /// Gets the search term matched against the words of the page texts, for finding its occurrences
+    fn occurrence_keyword(&self, search_mode: SearchMode) -> String {
+        let mut keywords: Vec<String> = Vec::new();
+        self.content_keywords(search_mode, &mut keywords);
+
+        match search_mode {
+            SearchMode::Regex => keywords.iter().map(|s| format!("(?:{})", s.trim())).collect::<Vec<String>>().join("|"),
+            _ => keywords.join(" ")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Builds a boolean combination of typed search queries
+pub struct QueryBuilder {
+    clauses: Vec<(Occur, SearchQuery)>,
+}
+
+impl QueryBuilder {
+    /// Creates a builder without any clause
+    pub fn new() -> Self {
+        QueryBuilder::default()
+    }
+
+    /// Adds a query that matched pages must match
+    pub fn must(mut self, query: SearchQuery) -> Self {
+        self.clauses.push((Occur::Must, query));
+        self
+    }
+
+    /// Adds a query that matched pages should match (at least one of them if there is no `must` clause)
+    pub fn should(mut self, query: SearchQuery) -> Self {
+        self.clauses.push((Occur::Should, query));
+        self
+    }
+
+    /// Adds a query that matched pages must not match
+    pub fn must_not(mut self, query: SearchQuery) -> Self {
+        self.clauses.push((Occur::MustNot, query));
+        self
+    }
+
+    /// Builds the boolean query from the added clauses
+    pub fn build(self) -> SearchQuery {
+        SearchQuery::Boolean(self.clauses)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Defines a single occurrence of the search term in the text of a PDF page
+pub struct KeywordOccurrence {
+    /// Page number containing the occurrence
+    pub page_num: u32,
+    /// Character offset of the matched word in the page text
+    pub start: usize,
+    /// Character offset right after the matched word in the page text
+    pub end: usize,
+    /// Matched word as written in the PDF document (may differ from the search term, e.g. in case or in fuzzy mode)
+    pub term: String,
+}
+
+#[derive(Debug, Clone)]
+/// Defines the highlighted snippet of a matched PDF page
+pub struct PageSnippet {
+    /// Page number of the snippet
+    pub page_num: u32,
+    /// Best matching excerpt of the page text (whitespace collapsed into single spaces)
+    pub fragment: String,
+    /// Character ranges (start, end) of the highlighted words in `fragment`
+    pub highlighted: Vec<(usize, usize)>,
+}
+
+impl PageSnippet {
+    /// Renders the snippet with the highlighted words wrapped in the given markup
+    /// 
+    /// ## Input Parameters
+    /// - `start_tag` contains the markup inserted before every highlighted word, e.g. `<b>`
+    /// - `end_tag` contains the markup inserted after every highlighted word, e.g. `</b>`
+    /// 
+    /// ## Returns
+    /// - Snippet text with highlighted words wrapped in the markup
+    pub fn render(&self, start_tag: &str, end_tag: &str) -> String {
+        let mut rendered: String = String::new();
+        for (idx, c) in self.fragment.chars().enumerate() {
+            if self.highlighted.iter().any(|s| s.0 == idx) {
+                rendered.push_str(start_tag);
+            }
+            rendered.push(c);
+            if self.highlighted.iter().any(|s| s.1 == idx + 1) {
+                rendered.push_str(end_tag);
+            }
+        }
+
+        rendered
+    }
+}
+
+#[derive(Debug, Clone)]
 /// Defines the metadata for extracted information from PDF files
 pub struct PDFMetadata {
     /// Name of the PDF file
     pub doc_name: String,
+    /// All indexed paths of the PDF file (identical copies are only indexed once)
+    pub locations: Vec<String>,
     /// Number of pages in the PDF file
     pub num_pages: usize,
+    /// BM25 relevance score of the PDF file (score of its best matched page)
+    pub score: f32,
+    /// BM25 relevance score of every matched page, as (page number, score), ranked by score
+    pub page_scores: Vec<(u32, f32)>,
     /// Matched page numbers containing the search term
     pub matched_page_nums: Vec<u32>,
-    /// Surrounding texts around the search term
-    pub cropped_texts: Vec<String>
+    /// Every occurrence of the search term on the matched pages
+    pub occurrences: Vec<KeywordOccurrence>,
+    /// Highlighted snippet of every matched page, in order of relevance
+    pub snippets: Vec<PageSnippet>,
+    /// Title of the PDF document
+    pub title: Option<String>,
+    /// Author(s) of the PDF document
+    pub author: Option<String>,
+    /// Subject of the PDF document
+    pub subject: Option<String>,
+    /// Keywords associated with the PDF document
+    pub keywords: Option<String>,
+    /// Application which produced the PDF document
+    pub producer: Option<String>,
+    /// Creation date of the PDF document (RFC 3339)
+    pub creation_date: Option<String>,
+    /// Last modification date of the PDF document (RFC 3339)
+    pub mod_date: Option<String>
 }
 
 impl PDFMetadata {
     /// Displays the metadata information based on search performed on PDF files
+    /// 
+    /// Highlighted words are coloured when writing to a terminal, and wrapped as `**word**` otherwise.
     pub fn show(&self) {
         println!("==================================================");
         println!("Document Name: {}", self.doc_name);
+        if self.locations.len() > 1 {
+            println!("Locations: {}", self.locations.join(", "));
+        }
         println!("Number of pages: {}", self.num_pages);
+        println!("Relevance Score: {:.4}", self.score);
+
+        let metadata = [
+            ("Title", &self.title),
+            ("Author", &self.author),
+            ("Subject", &self.subject),
+            ("Keywords", &self.keywords),
+            ("Producer", &self.producer),
+            ("Creation Date", &self.creation_date),
+            ("Modification Date", &self.mod_date),
+        ];
+        for (name, value) in metadata {
+            if let Some(s) = value {
+                println!("{}: {}", name, s);
+            }
+        }
+
         println!("Search Results:");
 
-        for (idx, page) in self.matched_page_nums.iter().enumerate(){
-            println!("[Page: {}] {}", page, self.cropped_texts[idx]);
+        let (start_tag, end_tag) = if std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+            (HIGHLIGHT_COLOR_START, HIGHLIGHT_COLOR_END)
+        } else {
+            ("**", "**")
+        };
+        for snippet in &self.snippets {
+            println!("[Page: {}] {}", snippet.page_num, snippet.render(start_tag, end_tag));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Defines a single page matched by a keyword search
+pub struct PageHit {
+    /// Name of the PDF file (first of its indexed locations)
+    pub doc_name: String,
+    /// All indexed paths of the PDF file sharing the matched content
+    pub locations: Vec<String>,
+    /// Content hash of the PDF file
+    pub content_hash: String,
+    /// Matched page number containing the search term
+    pub page_num: u32,
+    /// Detected language of the matched page (None if not detected)
+    pub language: Option<String>,
+    /// BM25 relevance score of the matched page
+    pub score: f32,
+    /// Number of pages in the PDF file
+    pub num_pages: usize,
+    /// Document-level metadata of the PDF file
+    pub document_info: DocumentInfo,
+    /// Every occurrence of the search term on the matched page
+    pub occurrences: Vec<KeywordOccurrence>,
+    /// Highlighted snippet of the matched page (None if no word of the page text is highlighted, e.g. for metadata-only matches)
+    pub snippet: Option<PageSnippet>
+}
+
+#[derive(Debug, Clone)]
+/// Defines the matched pages of a keyword search, within the requested limit and offset
+pub struct SearchResults {
+    /// Total number of pages matching the search term (regardless of limit and offset)
+    pub total_hits: usize,
+    /// Matched pages within the requested limit and offset, ranked by relevance score
+    pub page_hits: Vec<PageHit>,
+}
+
+#[derive(Debug, Clone)]
+/// Defines the matched PDF files of a keyword search, within the requested limit and offset
+pub struct PDFSearchResults {
+    /// Total number of pages matching the search term (regardless of limit and offset)
+    pub total_hits: usize,
+    /// Metadata of the PDF files containing the returned matched pages
+    pub documents: Vec<PDFMetadata>,
+}
+
+/// Gets a field from the index schema
+fn get_field(schema: &Schema, field_name: &str) -> Result<Field, SearchingError> {
+    match schema.get_field(field_name) {
+        Ok(s) => Ok(s),
+        Err(e) => Err(SearchingError::IndexFieldNotFound(field_name.to_string(), e))
+    }
+}
+
+/// Reads all indexed paths of the PDF files sharing a content hash
+fn read_content_locations(content_hash: &str, searcher: &Searcher) -> Result<Vec<String>, SearchingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+    let path_field = get_field(schema, "path")?;
+
+    let query = BooleanQuery::new(vec![
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_FILE), IndexRecordOption::Basic)) as Box<dyn Query>),
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(content_hash_field, content_hash), IndexRecordOption::Basic))),
+    ]);
+    let doc_addresses = match searcher.search(&query, &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(SearchingError::KeywordSearchError(e))
+    };
+
+    let mut locations: Vec<String> = Vec::new();
+    for doc_address in doc_addresses {
+        let retrieved_doc = match searcher.doc(doc_address) {
+            Ok(s) => s,
+            Err(e) => return Err(SearchingError::SearcherDocumentFetchError(e))
+        };
+
+        if let Some(s) = retrieved_doc.get_first(path_field).and_then(|v| v.as_text()) {
+            locations.push(s.to_string());
+        }
+    }
+    locations.sort();
+
+    Ok(locations)
+}
+
+/// Reads the indexed paths covered by the given scopes, grouped by the content hash of their PDF files
+/// 
+/// Candidate paths are looked up through the `path_facet` field of the file location documents,
+/// so only the files below the scoped directories are read; the maximum depth, include/exclude
+/// and glob patterns of every scope are then checked on the candidate paths.
+fn read_scoped_locations(path_scopes: &[PathScope], searcher: &Searcher) -> Result<HashMap<String, Vec<String>>, SearchingError> {
+    let schema = searcher.schema();
+    let doc_kind_field = get_field(schema, "doc_kind")?;
+    let path_field = get_field(schema, "path")?;
+    let path_facet_field = get_field(schema, "path_facet")?;
+    let content_hash_field = get_field(schema, "content_hash")?;
+
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![
+        (Occur::Must, Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_FILE), IndexRecordOption::Basic))),
+    ];
+
+    // Scopes without a literal base directory (e.g. `*.pdf`) cover all indexed files
+    if path_scopes.iter().all(|s| !s.base_steps().is_empty()) {
+        let facet_queries: Vec<Box<dyn Query>> = path_scopes
+            .iter()
+            .map(|s| Box::new(TermQuery::new(Term::from_facet(path_facet_field, &path_facet(&s.base_steps().join("/"))), IndexRecordOption::Basic)) as Box<dyn Query>)
+            .collect();
+        clauses.push((Occur::Must, any_of(facet_queries)));
+    }
+
+    let doc_addresses = match searcher.search(&BooleanQuery::new(clauses), &DocSetCollector) {
+        Ok(s) => s,
+        Err(e) => return Err(SearchingError::KeywordSearchError(e))
+    };
+
+    let mut scoped_locations: HashMap<String, Vec<String>> = HashMap::new();
+    for doc_address in doc_addresses {
+        let retrieved_doc = match searcher.doc(doc_address) {
+            Ok(s) => s,
+            Err(e) => return Err(SearchingError::SearcherDocumentFetchError(e))
+        };
+
+        let path: &str = retrieved_doc.get_first(path_field).and_then(|v| v.as_text()).unwrap_or_default();
+        if !path_scopes.iter().any(|s| s.contains(path)) {
+            continue;
+        }
+
+        let content_hash: &str = retrieved_doc.get_first(content_hash_field).and_then(|v| v.as_text()).unwrap_or_default();
+        scoped_locations.entry(content_hash.to_string()).or_default().push(path.to_string());
+    }
+
+    for locations in scoped_locations.values_mut() {
+        locations.sort();
+    }
+    trace!(target:"other_logging", "{} indexed content(s) found within the search scope.", scoped_locations.len());
+
+    Ok(scoped_locations)
+}
+
+/// Computes the edit distance between two words, counting a transposition of adjacent characters as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<Vec<usize>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost: usize = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance: usize = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Converts a wildcard pattern (`*` for any characters, `?` for a single character) into a regular expression
+fn wildcard_to_regex(pattern: &str) -> String {
+    let mut regex: String = String::new();
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&c.to_string()))
+        }
+    }
+
+    regex
+}
+
+/// Gets the regular expressions (each covering a whole word) of a wildcard or regex search
+fn keyword_patterns(keyword: &str, search_options: &SearchOptions, lowercase: bool) -> Vec<String> {
+    match search_options.mode {
+        SearchMode::Wildcard => keyword
+            .split_whitespace()
+            .map(|s| if lowercase { wildcard_to_regex(&s.to_lowercase()) } else { wildcard_to_regex(s) })
+            .collect(),
+        SearchMode::Regex => vec![keyword.trim().to_string()],
+        SearchMode::Exact | SearchMode::Fuzzy => Vec::new()
+    }
+}
+
+/// Checks if the analyzer of a field lowercases its tokens
+fn field_lowercases(index: &tantivy::Index, field: Field) -> Result<bool, SearchingError> {
+    let mut text_analyzer = match index.tokenizer_for_field(field) {
+        Ok(s) => s,
+        Err(e) => return Err(SearchingError::KeywordSearchError(e))
+    };
+    let mut token_stream = text_analyzer.token_stream("Q");
+
+    Ok(token_stream.advance() && token_stream.token().text == "q")
+}
+
+/// Checks that a wildcard or regex search doesn't use query syntax, which would otherwise be matched literally
+/// 
+/// Boolean operators, required (`+`) or excluded (`-`) terms and field-scoped terms are rejected in both modes.
+/// Parentheses and quotes are also rejected in wildcard mode, as they are part of the regex syntax otherwise.
+fn check_pattern_syntax(keyword: &str, mode: SearchMode) -> Result<(), SearchingError> {
+    for word in keyword.split_whitespace() {
+        let is_query_syntax: bool = matches!(word, "AND" | "OR" | "NOT")
+            || word.starts_with(['+', '-'])
+            || word.split_once(':').is_some_and(|(field, _)| !field.is_empty() && field.chars().all(|c| c.is_alphanumeric() || c == '_'))
+            || (mode == SearchMode::Wildcard && word.contains(['(', ')', '"']));
+
+        if is_query_syntax {
+            return Err(SearchingError::InvalidSearchOptions(format!("`{}` is query syntax, which is not supported by wildcard and regex searches; use an exact search or a typed query instead", word)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a query matching the indexed words of the content fields against wildcard or regex patterns
+fn build_pattern_query(index: &tantivy::Index, query_str: &str, fields: &[Field], search_options: &SearchOptions) -> Result<Box<dyn Query>, SearchingError> {
+    check_pattern_syntax(query_str, search_options.mode)?;
+
+    let mut subqueries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    for &field in fields {
+        // Wildcard patterns are normalized the same way as the indexed words
+        let lowercase: bool = field_lowercases(index, field)?;
+
+        for pattern in keyword_patterns(query_str, search_options, lowercase) {
+            match RegexQuery::from_pattern(&pattern, field) {
+                Ok(s) => subqueries.push((Occur::Should, Box::new(s))),
+                Err(e) => return Err(SearchingError::InvalidSearchPattern(pattern, e))
+            };
+        }
+    }
+
+    Ok(Box::new(BooleanQuery::new(subqueries)))
+}
+
+/// Gets the words of a search term to be looked up in the PDF text, skipping query operators and field-scoped terms
+fn keyword_words(keyword: &str) -> Vec<&str> {
+    keyword
+        .split_whitespace()
+        .filter(|s| !matches!(*s, "AND" | "OR" | "NOT") && !s.contains(':'))
+        .map(|s| s.trim_start_matches(['+', '-']))
+        .collect()
+}
+
+/// Tokenizes a text with the given analyzer
+fn tokenize(text_analyzer: &mut TextAnalyzer, text: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut token_stream = text_analyzer.token_stream(text);
+    while token_stream.advance() {
+        tokens.push(token_stream.token().clone());
+    }
+
+    tokens
+}
+
+/// Gets the analyzer the index used for tokenizing a content field
+fn field_text_analyzer(index: &tantivy::Index, field: Field) -> TextAnalyzer {
+    match index.tokenizer_for_field(field) {
+        Ok(s) => s,
+        Err(e) => {
+            warn!(target:"other_logging", "No analyzer found for page contents. Using default analyzer. -> {}", e);
+            index.tokenizers().get("default").unwrap_or_default()
+        }
+    }
+}
+
+/// Reads the document-level metadata stored with a page document
+fn read_stored_document_info(retrieved_doc: &Document, schema: &Schema) -> Result<DocumentInfo, SearchingError> {
+    let read_text = |field_name: &str| -> Result<Option<String>, SearchingError> {
+        let field = get_field(schema, field_name)?;
+        Ok(retrieved_doc.get_first(field).and_then(|v| v.as_text()).map(|s| s.to_string()))
+    };
+    let read_date = |field_name: &str| -> Result<Option<DateTime<Utc>>, SearchingError> {
+        let field = get_field(schema, field_name)?;
+        Ok(retrieved_doc
+            .get_first(field)
+            .and_then(|v| v.as_date())
+            .and_then(|d| Utc.timestamp_opt(d.into_timestamp_secs(), 0).single()))
+    };
+
+    Ok(DocumentInfo {
+        title: read_text("title")?,
+        author: read_text("author")?,
+        subject: read_text("subject")?,
+        keywords: read_text("keywords")?,
+        producer: read_text("producer")?,
+        creation_date: read_date("creation_date")?,
+        mod_date: read_date("mod_date")?,
+    })
+}
+
+/// Converts a snippet fragment and its highlighted byte ranges into a page snippet
+/// 
+/// Whitespace runs (such as line breaks of the PDF text) are collapsed into single spaces,
+/// and the highlighted ranges are converted into character offsets of the collapsed text.
+fn to_page_snippet(page_num: u32, fragment: &str, highlighted: &[Range<usize>]) -> PageSnippet {
+    let mut collapsed: String = String::new();
+    let mut num_chars: usize = 0;
+    let mut char_offsets: Vec<usize> = vec![0; fragment.len() + 1];
+
+    for (offset, c) in fragment.char_indices() {
+        char_offsets[offset] = num_chars;
+        if !c.is_whitespace() {
+            collapsed.push(c);
+            num_chars += 1;
+        } else if !collapsed.is_empty() && !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+            num_chars += 1;
+        }
+    }
+    char_offsets[fragment.len()] = num_chars;
+
+    if collapsed.ends_with(' ') {
+        collapsed.pop();
+        num_chars -= 1;
+    }
+
+    let highlighted: Vec<(usize, usize)> = highlighted
+        .iter()
+        .filter(|s| fragment.is_char_boundary(s.start) && fragment.is_char_boundary(s.end))
+        .map(|s| (char_offsets[s.start].min(num_chars), char_offsets[s.end].min(num_chars)))
+        .filter(|s| s.0 < s.1)
+        .collect();
+
+    PageSnippet { page_num, fragment: collapsed, highlighted }
+}
+
+/// Generates the highlighted snippet of a page text with Tantivy's snippet generator
+fn generate_snippet(searcher: &Searcher, query: &dyn Query, field: Field, page_num: u32, text: &str) -> Result<PageSnippet, SearchingError> {
+    let mut snippet_generator = match SnippetGenerator::create(searcher, query, field) {
+        Ok(s) => s,
+        Err(e) => return Err(SearchingError::SnippetGeneratorError(e))
+    };
+    snippet_generator.set_max_num_chars(SNIPPET_MAX_CHARS);
+
+    let snippet = snippet_generator.snippet(text);
+
+    Ok(to_page_snippet(page_num, snippet.fragment(), snippet.highlighted()))
+}
+
+/// Generates the highlighted snippet of a matched page from its stored text
+/// 
+/// The words are highlighted from the terms of the query. Fuzzy, wildcard and regex
+/// queries don't expose their matched terms, so the snippet then highlights the
+/// occurrences found in the page text instead.
+fn highlight_page(searcher: &Searcher, query: &dyn Query, field: Field, page_num: u32, text: &str, occurrences: &[KeywordOccurrence], text_analyzer: &mut TextAnalyzer) -> Result<Option<PageSnippet>, SearchingError> {
+    let snippet: PageSnippet = generate_snippet(searcher, query, field, page_num, text)?;
+    if !snippet.highlighted.is_empty() {
+        return Ok(Some(snippet));
+    }
+    if occurrences.is_empty() {
+        return Ok(None);
+    }
+
+    // Highlight the indexed terms of the words matched in the page text
+    let mut terms: Vec<String> = Vec::new();
+    for occurrence in occurrences {
+        terms.extend(tokenize(text_analyzer, &occurrence.term).into_iter().map(|s| s.text));
+    }
+    terms.sort();
+    terms.dedup();
+
+    let term_query = BooleanQuery::new_multiterms_query(terms
+        .iter()
+        .map(|s| Term::from_field_text(field, s))
+        .collect());
+    let snippet: PageSnippet = generate_snippet(searcher, &term_query, field, page_num, text)?;
+
+    if snippet.highlighted.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(snippet))
+    }
+}
+
+/// Defines how the tokens of a PDF text are matched against the search term while extracting snippets
+enum KeywordMatcher {
+    Exact(HashSet<String>),
+    Fuzzy(Vec<String>, usize),
+    Pattern(Vec<Regex>),
+}
+
+impl KeywordMatcher {
+    /// Creates the matcher for a search term, normalizing its words with the analyzer of the PDF text
+    fn new(keyword: &str, search_options: &SearchOptions, text_analyzer: &mut TextAnalyzer) -> Self {
+        let words: Vec<&str> = keyword_words(keyword);
+        let mut normalized_words: Vec<String> = Vec::new();
+        for word in &words {
+            normalized_words.extend(tokenize(text_analyzer, word).into_iter().map(|s| s.text));
+        }
+
+        match search_options.mode {
+            SearchMode::Exact => KeywordMatcher::Exact(normalized_words.into_iter().collect()),
+            SearchMode::Fuzzy => KeywordMatcher::Fuzzy(normalized_words, search_options.fuzzy_distance as usize),
+            SearchMode::Wildcard | SearchMode::Regex => {
+                let mut regexes: Vec<Regex> = Vec::new();
+                for pattern in keyword_patterns(keyword, search_options, true) {
+                    match Regex::new(&format!("^(?:{})$", pattern)) {
+                        Ok(s) => regexes.push(s),
+                        Err(e) => warn!(target:"other_logging", "Unable to match search pattern `{}` in PDF text -> {}", &pattern, e)
+                    };
+                }
+                KeywordMatcher::Pattern(regexes)
+            }
+        }
+    }
+
+    /// Checks if a token of the PDF text matches the search term
+    /// 
+    /// `token` is the token normalized by the analyzer, while `word` is the text it was read from.
+    fn matches(&self, token: &str, word: &str) -> bool {
+        match self {
+            KeywordMatcher::Exact(keywords) => keywords.contains(token),
+            KeywordMatcher::Fuzzy(keywords, distance) => keywords.iter().any(|s| edit_distance(token, s) <= *distance),
+            KeywordMatcher::Pattern(regexes) => {
+                let lowercase_word: String = word.to_lowercase();
+                regexes.iter().any(|r| r.is_match(token) || r.is_match(word) || r.is_match(&lowercase_word))
+            }
         }
     }
 }
 
+/// Finds every occurrence of the search term in the text of a PDF page
+/// 
+/// The text is tokenized with the analyzer used by the index, so that matching is
+/// case-insensitive (unless the index is case-sensitive) and ignores punctuation
+/// attached to words.
+/// 
+/// ## Input Parameters
+/// - `text` contains the text of a single PDF page
+/// - `page_num` contains the page number of the text
+/// - `keyword` contains the search term
+/// - `search_options` defines how the search term is matched against the words of the PDF text
+/// - `text_analyzer` contains the analyzer used for tokenizing the PDF text
+/// 
+/// ## Returns
+/// - Vector of occurrences of the search term, in order of appearance
+pub fn find_keyword_occurrences(text: &str, page_num: u32, keyword: &str, search_options: &SearchOptions, text_analyzer: &mut TextAnalyzer) -> Vec<KeywordOccurrence> {
+    let keyword_matcher: KeywordMatcher = KeywordMatcher::new(keyword, search_options, text_analyzer);
+    let tokens: Vec<Token> = tokenize(text_analyzer, text);
+    let mut occurrences: Vec<KeywordOccurrence> = Vec::new();
+
+    for token in &tokens {
+        let word: &str = match text.get(token.offset_from..token.offset_to) {
+            Some(s) => s,
+            None => continue
+        };
+        if !keyword_matcher.matches(&token.text, word) {
+            continue;
+        }
+
+        // Convert byte offsets into character offsets
+        let start: usize = text[..token.offset_from].chars().count();
+        let end: usize = start + word.chars().count();
+
+        occurrences.push(KeywordOccurrence { page_num, start, end, term: word.to_string() });
+    }
+
+    occurrences
+}
+
+/// Gets the fields searched by a typed query, given its field scope
+fn scope_fields(schema: &Schema, field_name: &Option<String>, default_fields: &[Field]) -> Result<Vec<Field>, SearchingError> {
+    let field_name: &str = match field_name.as_deref() {
+        None | Some("content") => return Ok(default_fields.to_vec()),
+        Some(s) => s
+    };
+
+    let field: Field = get_field(schema, field_name)?;
+    match schema.get_field_entry(field).field_type() {
+        FieldType::Str(_) => Ok(vec![field]),
+        _ => Err(SearchingError::InvalidSearchOptions(format!("field `{}` is not a text field; use a raw query to search it", field_name)))
+    }
+}
+
+/// Tokenizes the words of a typed query with the analyzer of a field, keeping their positions
+fn field_terms(index: &tantivy::Index, field: Field, words: &str) -> Vec<(usize, Term)> {
+    let mut text_analyzer: TextAnalyzer = field_text_analyzer(index, field);
+
+    tokenize(&mut text_analyzer, words)
+        .into_iter()
+        .map(|s| (s.position, Term::from_field_text(field, &s.text)))
+        .collect()
+}
+
+/// Builds a query matching a sequence of terms, allowing them to be moved by up to `slop` positions
+fn terms_query(terms: Vec<(usize, Term)>, slop: u32) -> Option<Box<dyn Query>> {
+    match terms.len() {
+        0 => None,
+        1 => Some(Box::new(TermQuery::new(terms[0].1.clone(), IndexRecordOption::WithFreqsAndPositions))),
+        _ => {
+            // Positions are relative to the first word, keeping the gaps of removed stop words
+            let first_position: usize = terms[0].0;
+            let mut phrase_query = PhraseQuery::new_with_offset(terms
+                .into_iter()
+                .map(|(position, term)| (position - first_position, term))
+                .collect());
+            phrase_query.set_slop(slop);
+            Some(Box::new(phrase_query))
+        }
+    }
+}
+
+/// Combines alternative queries, any of which may match
+fn any_of(queries: Vec<Box<dyn Query>>) -> Box<dyn Query> {
+    Box::new(BooleanQuery::new(queries.into_iter().map(|s| (Occur::Should, s)).collect()))
+}
+
+/// Builds a query from a raw query string, according to the search mode
+fn build_raw_query(index: &tantivy::Index, query_str: &str, default_fields: &[Field], search_options: &SearchOptions) -> Result<Box<dyn Query>, SearchingError> {
+    match search_options.mode {
+        SearchMode::Exact | SearchMode::Fuzzy => {
+            let mut query_parser = QueryParser::for_index(index, default_fields.to_vec());
+            debug!(target:"other_logging", "Query parser created successfully for `content` fields.");
+
+            // Turn the terms searched in page contents into fuzzy term queries
+            if search_options.mode == SearchMode::Fuzzy {
+                for &field in default_fields {
+                    query_parser.set_field_fuzzy(field, false, search_options.fuzzy_distance, true);
+                }
+                debug!(target:"other_logging", "Fuzzy matching enabled with distance {}.", search_options.fuzzy_distance);
+            }
+
+            // Parse the query string
+            match query_parser.parse_query(query_str) {
+                Ok(s) => Ok(s),
+                Err(e) => Err(SearchingError::QueryParserError(e))
+            }
+        },
+        SearchMode::Wildcard | SearchMode::Regex => build_pattern_query(index, query_str, default_fields, search_options)
+    }
+}
+
+/// Builds the Tantivy query of a typed search query
+/// 
+/// Single words are matched according to the search mode, while phrases and proximity
+/// queries always match their words exactly.
+fn build_query(index: &tantivy::Index, search_query: &SearchQuery, default_fields: &[Field], search_options: &SearchOptions) -> Result<Box<dyn Query>, SearchingError> {
+    let schema = index.schema();
+
+    let query: Box<dyn Query> = match search_query {
+        SearchQuery::Raw(s) => build_raw_query(index, s, default_fields, search_options)?,
+        SearchQuery::Term { word, field } => {
+            let mut queries: Vec<Box<dyn Query>> = Vec::new();
+            for field in scope_fields(&schema, field, default_fields)? {
+                match search_options.mode {
+                    SearchMode::Exact => queries.extend(terms_query(field_terms(index, field, word), 0)),
+                    SearchMode::Fuzzy => {
+                        let fuzzy_queries: Vec<(Occur, Box<dyn Query>)> = field_terms(index, field, word)
+                            .into_iter()
+                            .map(|(_, term)| (Occur::Must, Box::new(FuzzyTermQuery::new(term, search_options.fuzzy_distance, true)) as Box<dyn Query>))
+                            .collect();
+                        if !fuzzy_queries.is_empty() {
+                            queries.push(Box::new(BooleanQuery::new(fuzzy_queries)));
+                        }
+                    },
+                    SearchMode::Wildcard | SearchMode::Regex => queries.push(build_pattern_query(index, word, &[field], search_options)?)
+                };
+            }
+            any_of(queries)
+        },
+        SearchQuery::Phrase { words, field, slop } => {
+            let mut queries: Vec<Box<dyn Query>> = Vec::new();
+            for field in scope_fields(&schema, field, default_fields)? {
+                queries.extend(terms_query(field_terms(index, field, words), *slop));
+            }
+            any_of(queries)
+        },
+        SearchQuery::Proximity { first, second, distance, field } => {
+            let mut queries: Vec<Box<dyn Query>> = Vec::new();
+            for field in scope_fields(&schema, field, default_fields)? {
+                let first_terms: Vec<(usize, Term)> = field_terms(index, field, first);
+                let second_terms: Vec<(usize, Term)> = field_terms(index, field, second);
+
+                // The distance would otherwise also be spent within multi-word operands
+                if first_terms.len() > 1 || second_terms.len() > 1 {
+                    return Err(SearchingError::InvalidSearchOptions(format!("proximity operands must be single words, got `{}` and `{}`; use a phrase with a slop instead", first, second)));
+                }
+                let (first_term, second_term) = match (first_terms.first(), second_terms.first()) {
+                    (Some((_, first_term)), Some((_, second_term))) => (first_term, second_term),
+                    _ => continue
+                };
+
+                // Match both orders, as phrase queries keep the order of their words
+                for (left, right) in [(first_term, second_term), (second_term, first_term)] {
+                    queries.extend(terms_query(vec![(0, left.clone()), (1, right.clone())], *distance));
+                }
+            }
+            any_of(queries)
+        },
+        SearchQuery::Boolean(clauses) => {
+            let mut subqueries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+            for (occur, query) in clauses {
+                subqueries.push((*occur, build_query(index, query, default_fields, search_options)?));
+            }
+            Box::new(BooleanQuery::new(subqueries))
+        },
+        SearchQuery::Boost(query, boost) => Box::new(BoostQuery::new(build_query(index, query, default_fields, search_options)?, *boost))
+    };
+
+    Ok(query)
+}
+
 /// Searches the given keyword in indexed files
 /// 
+/// Besides the page contents, queries may be scoped to the document metadata fields
+/// (`title`, `author`, `subject`, `keywords`, `producer`, `creation_date`, `mod_date`),
+/// e.g. `author:girshick` or `creation_date:[2023-01-01T00:00:00Z TO 2024-01-01T00:00:00Z]`.
+/// Every hit lists all paths where its PDF content was indexed. Pages can be filtered
+/// by their detected language, e.g. `network AND language:german`; the search term is
+/// matched against the language-specific content fields as well, each with its own analyzer.
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index for performing the search
+/// - `query_str` contains the keyword to be searched in PDF files
+/// 
+/// ## Returns
+/// - Vector of the 10 best matched PDF pages (containing the search term), ranked by relevance score
+pub fn search_keyword(index: &tantivy::Index, query_str: &str) -> Result<Vec<PageHit>, SearchingError> {
+    search_keyword_with_options(index, query_str, &SearchOptions::default()).map(|s| s.page_hits)
+}
+
+/// Searches the given keyword in indexed files, using the given search mode
+/// 
+/// In fuzzy mode, every term of the query is matched with a Tantivy `FuzzyTermQuery`
+/// against the page contents, tolerating up to `fuzzy_distance` edits. In wildcard and
+/// regex modes, the query is matched against the indexed words of the page contents with
+/// Tantivy `RegexQuery`s; as indexed words are normalized by the analyzer (lowercased by
+/// default), regular expressions should be written for the normalized words.
+/// 
+/// Only the `limit` best matched pages after skipping the first `offset` ones are returned,
+/// along with the total number of matched pages, so that results can be paged through.
+/// 
 /// ## Input Parameters
 /// - `index` contains the Tantivy index for performing the search
 /// - `query_str` contains the keyword to be searched in PDF files
+/// - `search_options` defines how the search terms are matched and which page of results is returned
+/// 
+/// ## Returns
+/// - `SearchResults` struct containing the matched PDF pages (ranked by relevance score) and the total number of matched pages
+pub fn search_keyword_with_options(index: &tantivy::Index, query_str: &str, search_options: &SearchOptions) -> Result<SearchResults, SearchingError> {
+    search_query(index, &SearchQuery::parse(query_str), search_options)
+}
+
+/// Searches a typed query in indexed files
+/// 
+/// Raw query strings are parsed as with [`search_keyword_with_options`], while the other queries
+/// are built without Tantivy's query syntax, so that their words are never read as operators.
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index for performing the search
+/// - `search_query` contains the query to be searched in PDF files
+/// - `search_options` defines how single words are matched and which page of results is returned
 /// 
 /// ## Returns
-/// - Hashmap containing matched PDF documents and corresponding page numbers (containing the search term)
-pub fn search_keyword(index: &tantivy::Index, query_str: &str) -> Result<HashMap<String, Vec<String>>, SearchingError> {
+/// - `SearchResults` struct containing the matched PDF pages (ranked by relevance score) and the total number of matched pages
+pub fn search_query(index: &tantivy::Index, search_query: &SearchQuery, search_options: &SearchOptions) -> Result<SearchResults, SearchingError> {
+    search_query_in_scope(index, search_query, search_options, None)
+}
+
+/// Searches a typed query in the indexed files covered by the given path scopes
+/// 
+/// The scope is applied inside the index, before ranking, so the `limit` best matched pages
+/// (and the total number of matched pages) only count the PDF files within the scope.
+/// The name of every hit is its first path within the scope, while its locations still list all of its paths.
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index for performing the search
+/// - `search_query` contains the query to be searched in PDF files
+/// - `search_options` defines how single words are matched and which page of results is returned
+/// - `path_scopes` defines the PDF files, directories or glob patterns to be searched (None searches all indexed files)
+/// 
+/// ## Returns
+/// - `SearchResults` struct containing the matched PDF pages (ranked by relevance score) and the total number of matched pages
+pub fn search_query_in_scope(index: &tantivy::Index, search_query: &SearchQuery, search_options: &SearchOptions, path_scopes: Option<&[PathScope]>) -> Result<SearchResults, SearchingError> {
     // Create the index reader object
     let indexer = match index.reader() {
         Ok(s) => s,
@@ -59,119 +1110,302 @@ pub fn search_keyword(index: &tantivy::Index, query_str: &str) -> Result<HashMap
     let searcher = indexer.searcher();
     debug!(target:"other_logging", "Index searcher object created successfully.");
 
-    // Define the index field for running the search on
-    let content_field = match index.schema().get_field("content") {
-        Ok(s) => s,
-        Err(e) => return Err(SearchingError::IndexFieldNotFound(String::from("content"), e))
+    search_query_with_searcher(index, &searcher, search_query, search_options, path_scopes)
+}
+
+/// Searches a typed query in the indexed files covered by the given path scopes, using an existing index searcher
+/// 
+/// Same as [`search_query_in_scope`], for callers keeping an index reader open across searches.
+/// 
+/// ## Input Parameters
+/// - `index` contains the Tantivy index for performing the search
+/// - `searcher` contains the snapshot of `index` to be searched
+/// - `search_query` contains the query to be searched in PDF files
+/// - `search_options` defines how single words are matched and which page of results is returned
+/// - `path_scopes` defines the PDF files, directories or glob patterns to be searched (None searches all indexed files)
+/// 
+/// ## Returns
+/// - `SearchResults` struct containing the matched PDF pages (ranked by relevance score) and the total number of matched pages
+pub fn search_query_with_searcher(index: &tantivy::Index, searcher: &Searcher, search_query: &SearchQuery, search_options: &SearchOptions, path_scopes: Option<&[PathScope]>) -> Result<SearchResults, SearchingError> {
+    // Record the search as a span
+    let search_span: Span = info_span!("search", query = ?search_query, mode = ?search_options.mode, scoped = path_scopes.is_some(), hits = Empty, total_hits = Empty, duration_ms = Empty, error_code = Empty);
+    let started: Instant = Instant::now();
+
+    let result = search_span.in_scope(|| run_search(index, searcher, search_query, search_options, path_scopes));
+    match &result {
+        Ok(s) => {
+            search_span.record("hits", s.page_hits.len());
+            search_span.record("total_hits", s.total_hits);
+        },
+        Err(err) => {
+            search_span.record("error_code", err.code());
+        }
     };
-    let query_parser = QueryParser::for_index(&index, vec![content_field]);
-    debug!(target:"other_logging", "Query parser created successfully for `content` field.");
+    search_span.record("duration_ms", started.elapsed().as_millis() as u64);
 
-    // Parse the query string
-    let query = match query_parser.parse_query(query_str) {
-        Ok(s) => s,
-        Err(e) => return Err(SearchingError::QueryParserError(e))
+    result
+}
+
+/// Runs the search of [`search_query_with_searcher`] within the span of the search
+fn run_search(index: &tantivy::Index, searcher: &Searcher, search_query: &SearchQuery, search_options: &SearchOptions, path_scopes: Option<&[PathScope]>) -> Result<SearchResults, SearchingError> {
+    if search_options.mode == SearchMode::Fuzzy && (search_options.fuzzy_distance == 0 || search_options.fuzzy_distance > MAX_FUZZY_DISTANCE) {
+        return Err(SearchingError::InvalidSearchOptions(format!("fuzzy distance must be between 1 and {}, got {}", MAX_FUZZY_DISTANCE, search_options.fuzzy_distance)));
+    }
+
+    if search_options.limit == 0 {
+        return Err(SearchingError::InvalidSearchOptions(String::from("limit must be at least 1")));
+    }
+
+    match search_options.limit.checked_add(search_options.offset) {
+        Some(s) if s <= MAX_SEARCH_WINDOW => {},
+        _ => return Err(SearchingError::InvalidSearchOptions(format!("limit + offset must be at most {}, got limit {} and offset {}", MAX_SEARCH_WINDOW, search_options.limit, search_options.offset)))
     };
-    debug!(target:"other_logging", "Query parsing completed successfully for query string -> {}", &query_str);
 
-    // Search the index
-    let top_docs = match searcher.search(&query, &TopDocs::with_limit(10)) {
+    // Define the index fields for running the search on and reading the results from
+    let schema = index.schema();
+    let content_field = get_field(&schema, "content")?;
+    let content_hash_field = get_field(&schema, "content_hash")?;
+    let page_num_field = get_field(&schema, "page_num")?;
+    let num_pages_field = get_field(&schema, "num_pages")?;
+    let language_field = get_field(&schema, "language")?;
+
+    // Search the language-specific content fields (if any) besides the `content` field
+    let mut default_fields: Vec<Field> = vec![content_field];
+    default_fields.extend(SUPPORTED_LANGUAGES
+        .iter()
+        .filter_map(|s| schema.get_field(&content_field_name(s)).ok()));
+
+    let query: Box<dyn Query> = build_query(index, search_query, &default_fields, search_options)?;
+    debug!(target:"other_logging", "Query building completed successfully for query -> {:?}", search_query);
+
+    // Words looked up in the page texts for finding the occurrences of the query
+    let keyword: String = search_query.occurrence_keyword(search_options.mode);
+
+    // Restrict the matched pages to the contents of the scoped files, without changing their scores
+    let scoped_locations: Option<HashMap<String, Vec<String>>> = match path_scopes {
+        Some(s) => Some(read_scoped_locations(s, searcher)?),
+        None => None
+    };
+    // Only match page documents, e.g. for `*` or `path:` queries, and restrict the matched pages
+    // to the contents of the scoped files, without changing their scores
+    let doc_kind_field = get_field(&schema, "doc_kind")?;
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![
+        (Occur::Must, query.box_clone()),
+        (Occur::Must, Box::new(ConstScoreQuery::new(Box::new(TermQuery::new(Term::from_field_text(doc_kind_field, DOC_KIND_PAGE), IndexRecordOption::Basic)), 0.0))),
+    ];
+    match &scoped_locations {
+        Some(s) if s.is_empty() => return Ok(SearchResults { total_hits: 0, page_hits: Vec::new() }),
+        Some(s) => {
+            let content_hash_terms = s.keys().map(|k| Term::from_field_text(content_hash_field, k));
+            clauses.push((Occur::Must, Box::new(ConstScoreQuery::new(Box::new(TermSetQuery::new(content_hash_terms)), 0.0))));
+        },
+        None => {}
+    };
+    let scoped_query: Box<dyn Query> = Box::new(BooleanQuery::new(clauses));
+
+    // Search the index, counting all matched pages besides collecting the requested ones
+    // Never collect more pages than the index holds
+    let num_docs: usize = searcher.num_docs() as usize;
+    let offset: usize = std::cmp::min(search_options.offset, num_docs);
+    let limit: usize = std::cmp::min(search_options.limit, num_docs - offset).max(1);
+    let collectors = (TopDocs::with_limit(limit).and_offset(offset), Count);
+    let (top_docs, total_hits) = match searcher.search(&scoped_query, &collectors) {
         Ok(s) => s,
         Err(e) => return Err(SearchingError::KeywordSearchError(e))
     };
-    debug!(target:"other_logging", "Top 10 matched documents retrived from search.");
+    debug!(target:"other_logging", "{} matched page(s) retrived from search (limit: {}, offset: {}, total: {}).", top_docs.len(), search_options.limit, search_options.offset, total_hits);
 
-    // Capture search results
-    let mut doc_page_map: HashMap<String, Vec<String>> = HashMap::new();
+    // Capture search results (already ordered by descending score)
+    let mut page_hits: Vec<PageHit> = Vec::new();
+    let mut content_locations: HashMap<String, Vec<String>> = HashMap::new();
 
-    for (_score, doc_address) in top_docs {
+    for (score, doc_address) in top_docs {
         let retrieved_doc = match searcher.doc(doc_address) {
             Ok(s) => s,
             Err(e) => return Err(SearchingError::SearcherDocumentFetchError(e))
         };
 
-        let path_field = match index.schema().get_field("path") {
-            Ok(s) => s,
-            Err(e) => return Err(SearchingError::IndexFieldNotFound(String::from("path"), e))
-        };
+        let content_hash: String = retrieved_doc
+            .get_first(content_hash_field)
+            .and_then(|v| v.as_text())
+            .unwrap_or_default()
+            .to_string();
 
-        let page_num_field = match index.schema().get_field("page_num") {
-            Ok(s) => s,
-            Err(e) => return Err(SearchingError::IndexFieldNotFound(String::from("page_num"), e))
+        // Resolve the paths of the matched content (once per content hash)
+        let locations: Vec<String> = match content_locations.get(&content_hash) {
+            Some(s) => s.clone(),
+            None => {
+                let locations: Vec<String> = read_content_locations(&content_hash, searcher)?;
+                content_locations.insert(content_hash.clone(), locations.clone());
+                locations
+            }
         };
+        let doc_name: String = match scoped_locations.as_ref().and_then(|s| s.get(&content_hash)) {
+            Some(s) => s[0].clone(),
+            None => locations.first().cloned().unwrap_or_default()
+        };
+
+        let page_num: u32 = retrieved_doc
+            .get_first(page_num_field)
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default() as u32;
 
-        let doc_name = retrieved_doc
-            .get_first(path_field)
+        let language: Option<String> = retrieved_doc
+            .get_first(language_field)
             .and_then(|v| v.as_text())
-            .unwrap_or_default()
-            .to_string();
+            .map(|s| s.to_string());
 
-        let mut page_num: Vec<String> = Vec::new();
-        for item in retrieved_doc.get_all(page_num_field) {
-            page_num.push(item.as_text().unwrap().to_string());
-        }
-        
-        doc_page_map.insert(doc_name, page_num);
+        let num_pages: usize = retrieved_doc
+            .get_first(num_pages_field)
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default() as usize;
+
+        let document_info: DocumentInfo = read_stored_document_info(&retrieved_doc, &schema)?;
+
+        // Read the stored page text from the content field of its language (if the index has one)
+        let page_content_field: Field = match language.as_ref().map(|s| schema.get_field(&content_field_name(s))) {
+            Some(Ok(s)) => s,
+            _ => content_field
+        };
+        let text: &str = retrieved_doc
+            .get_first(page_content_field)
+            .and_then(|v| v.as_text())
+            .unwrap_or_default();
+
+        // Find the occurrences of the search term and highlight them in a snippet of the page
+        let mut text_analyzer: TextAnalyzer = field_text_analyzer(index, page_content_field);
+        let occurrences: Vec<KeywordOccurrence> = find_keyword_occurrences(text, page_num, &keyword, search_options, &mut text_analyzer);
+        let snippet: Option<PageSnippet> = highlight_page(searcher, &*query, page_content_field, page_num, text, &occurrences, &mut text_analyzer)?;
+        trace!(target:"other_logging", "`{}` page {} -> {} occurrence(s) of `{}`", &doc_name, page_num, occurrences.len(), &keyword);
+
+        page_hits.push(PageHit { doc_name, locations, content_hash, page_num, language, score, num_pages, document_info, occurrences, snippet });
     }
     
-    Ok(doc_page_map)
+    Ok(SearchResults { total_hits, page_hits })
 }
 
-/// Captures metadata information from PDF files based on search term provided
+/// Groups matched pages into the matched PDF files
+/// 
+/// Pages sharing the same PDF content are analysed together, named after their first
+/// location within the search scope, and the PDF files are ordered by the given sort key.
+/// Only the PDF files of the given pages are sorted, so the sort key applies within the
+/// returned page of results, not across all matched pages.
 /// 
 /// ## Input Parameters
-/// - `file` contains the PDF file for information extration
-/// - 
-/// - `keyword` contains the search term for extracting metadata information
-/// - `page_num` contains the matched page numbers in PDF document containing the search term
+/// - `search_results` contains the matched PDF pages (ranked by relevance score)
+/// - `sort_by` defines the order of the matched PDF files
 /// 
 /// ## Returns
-/// - `PDFMetadata` struct containing captured metadata information for matched PDF files (containing the search term)
-pub fn run_analysis(file: &String, page_num: &Vec<String>, keyword: &str) -> Result<PDFMetadata, FileOperationsError> {
-    // Read the PDF file
-    let doc = match lopdoc::load(file) {
-        Ok(s) => s,
-        Err(e) => return Err(FileOperationsError::PDFFileReadError(file.clone(), e))
-    };
+/// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+pub fn collect_documents(search_results: SearchResults, sort_by: SortBy) -> PDFSearchResults {
+    // Group the matched pages (ordered by relevance score) by unique PDF content
+    let mut matched_docs: HashMap<String, Vec<PageHit>> = HashMap::new();
+    for hit in search_results.page_hits {
+        matched_docs.entry(hit.content_hash.clone()).or_default().push(hit);
+    }
 
-    trace!(target:"other_logging", "PDF file `{}` with {} pages read successfully.", &file, &doc.get_pages().len());
+    // Run analysis on PDF documents containing the search term (from the contents stored in the index)
+    let mut metadata_vec: Vec<PDFMetadata> = Vec::new();
+    for hits in matched_docs.values() {
+        // Extract metadata information from matched PDF file (named after its first location within the scope)
+        let doc_name: &str = &hits[0].doc_name;
+        let metadata: PDFMetadata = run_analysis(doc_name, hits);
 
-    // Get all pages in the PDF file
-    let pages = doc.get_pages();
-    let num_pages: usize = pages.len();
-    let mut matched_page_nums: Vec<u32> = Vec::new();
-    let mut cropped_texts: Vec<String> = Vec::new();
+        info!(target:"info_logging", "{}: Metadata extracted successfully.", &doc_name);
+        trace!(target:"other_logging", "{}: {:?}", &doc_name, &metadata);
+        metadata_vec.push(metadata);
+    }
 
-    for item in page_num {
-        let p_num: u32 = item.trim().parse::<u32>().unwrap();
+    // Order the matched PDF files by relevance score, or by the requested sort key
+    sort_documents(&mut metadata_vec, sort_by);
 
-        // Extract text from matched PDF pages containing the search term
-        let text: String = match doc.extract_text(&[p_num]) {
-            Ok(s) => s,
-            Err(e) => return Err(FileOperationsError::PDFFileTextExtractionError(file.clone(), p_num, e))
-        };
-
-        // Extract surrounding text around the search term
-        if text.contains(keyword) {
-            matched_page_nums.push(p_num);
+    if metadata_vec.is_empty() {
+        info!(target:"info_logging", "No matching documents found");
+    }
 
-            let str_vec: Vec<&str> = text.split(' ').collect::<Vec<&str>>();
-            let index: i32 = str_vec.iter().position(|&r| r == keyword).unwrap() as i32;
-            
-            let lower_bound: i32 = std::cmp::max(0, index-20);
-            let upper_bound: i32 = std::cmp::min(index+21, str_vec.len() as i32);
+    PDFSearchResults { total_hits: search_results.total_hits, documents: metadata_vec }
+}
 
-            let cropped_vec: &[&str] = &str_vec[lower_bound as usize..upper_bound as usize];
-            let cropped_text: String = cropped_vec.join(" ");
+/// Captures metadata information from PDF files based on search term provided
+/// 
+/// The metadata is read from the matched pages of a single PDF content, as stored in
+/// the index, so the PDF file itself is not read again (and doesn't need to be reachable).
+/// 
+/// ## Input Parameters
+/// - `file` contains the name of the PDF file
+/// - `page_hits` contains the matched pages of the PDF file
+/// 
+/// ## Returns
+/// - `PDFMetadata` struct containing captured metadata information for matched PDF files (containing the search term)
+pub fn run_analysis(file: &str, page_hits: &[PageHit]) -> PDFMetadata {
+    let mut matched_page_nums: Vec<u32> = Vec::new();
+    let mut occurrences: Vec<KeywordOccurrence> = Vec::new();
+    let mut snippets: Vec<PageSnippet> = Vec::new();
 
-            cropped_texts.push(cropped_text)
+    for hit in page_hits {
+        // Skip pages matched by their metadata only
+        if let Some(s) = &hit.snippet {
+            matched_page_nums.push(hit.page_num);
+            occurrences.extend(hit.occurrences.iter().cloned());
+            snippets.push(s.clone());
         }
     }
+    trace!(target:"other_logging", "`{}` -> {} matched page(s), {} occurrence(s)", file, matched_page_nums.len(), occurrences.len());
+
+    let document_info: DocumentInfo = page_hits.first().map(|s| s.document_info.clone()).unwrap_or_default();
+
+    // Rank the pages by score, the document score being the one of its best page
+    let mut page_scores: Vec<(u32, f32)> = page_hits.iter().map(|s| (s.page_num, s.score)).collect();
+    page_scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let score: f32 = page_scores.first().map(|s| s.1).unwrap_or_default();
 
-    Ok(PDFMetadata{
-        doc_name: file.clone(),
-        num_pages, 
+    PDFMetadata{
+        doc_name: file.to_string(),
+        locations: page_hits.first().map(|s| s.locations.clone()).unwrap_or_else(|| vec![file.to_string()]),
+        num_pages: page_hits.first().map(|s| s.num_pages).unwrap_or_default(),
+        score,
+        page_scores,
         matched_page_nums, 
-        cropped_texts
-    })
-}
\ No newline at end of file
+        occurrences,
+        snippets,
+        title: document_info.title,
+        author: document_info.author,
+        subject: document_info.subject,
+        keywords: document_info.keywords,
+        producer: document_info.producer,
+        creation_date: document_info.creation_date.map(|d| d.to_rfc3339()),
+        mod_date: document_info.mod_date.map(|d| d.to_rfc3339())
+    }
+}
+
+/// Sorts the matched PDF files of a search
+/// 
+/// Ties are broken by relevance score, then by path. PDF files without a modification
+/// date come last when sorting by modification date.
+/// 
+/// ## Input Parameters
+/// - `documents` contains the metadata of the matched PDF files
+/// - `sort_by` defines the order of the matched PDF files
+/// 
+/// ## Returns
+/// - None
+pub fn sort_documents(documents: &mut [PDFMetadata], sort_by: SortBy) {
+    let by_relevance = |a: &PDFMetadata, b: &PDFMetadata| -> Ordering {
+        b.score.total_cmp(&a.score).then_with(|| a.doc_name.cmp(&b.doc_name))
+    };
+
+    match sort_by {
+        SortBy::Relevance => documents.sort_by(by_relevance),
+        SortBy::Path => documents.sort_by(|a, b| a.doc_name.cmp(&b.doc_name).then_with(|| by_relevance(a, b))),
+        SortBy::ModificationDate => documents.sort_by(|a, b| {
+            // Dates are stored in RFC 3339 format (UTC), so they compare chronologically as strings
+            match (&a.mod_date, &b.mod_date) {
+                (Some(x), Some(y)) => y.cmp(x),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal
+            }.then_with(|| by_relevance(a, b))
+        }),
+        SortBy::PageCount => documents.sort_by(|a, b| b.num_pages.cmp(&a.num_pages).then_with(|| by_relevance(a, b)))
+    };
+}
@@ -0,0 +1,344 @@
+//! # Seeker module
+//!
+//! - Defines the `PdfSeeker` handle, owning an open index for indexing, searching and removing PDF files
+//! - Defines the builder configuring the cache directory, analyzer, traversal and search defaults of a handle
+//! - Lists the indexed PDF files, index statistics and the stored text of indexed pages
+//! - Keeps a single index reader (and index writer, once needed) open across calls
+
+use crate::analyzer_operations::AnalyzerOptions;
+use crate::error::{IndexingError, PdfSeekerError};
+use crate::file_operations::*;
+use crate::index_operations::*;
+use crate::search_operations::*;
+use log::{debug, info};
+use std::path::{Path, PathBuf};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy};
+
+/// Converts a path into the string form stored in the index
+fn path_string<P: AsRef<Path>>(path: P) -> String {
+    path.as_ref().to_string_lossy().to_string()
+}
+
+#[derive(Debug, Clone, Default)]
+/// Defines the configuration of a `PdfSeeker` handle
+pub struct PdfSeekerBuilder {
+    cache_dir: Option<PathBuf>,
+    indexing_options: IndexingOptions,
+    traversal_options: TraversalOptions,
+    search_options: SearchOptions,
+    logging: bool,
+    log_level: Option<String>,
+    json_logs: bool,
+}
+
+impl PdfSeekerBuilder {
+    /// Creates a builder with the default configuration
+    pub fn new() -> Self {
+        PdfSeekerBuilder::default()
+    }
+
+    /// Sets the directory where indexed files, log files and tracker files are stored (current working directory by default)
+    pub fn cache_dir<P: AsRef<Path>>(mut self, cache_dir: P) -> Self {
+        self.cache_dir = Some(cache_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the text analyzer of a newly created index (existing indexes keep their analyzer)
+    pub fn analyzer(mut self, analyzer_options: AnalyzerOptions) -> Self {
+        self.indexing_options.analyzer = Some(analyzer_options);
+        self
+    }
+
+    /// Sets the worker pool, index writer and commit batch configuration
+    pub fn indexing_options(mut self, indexing_options: IndexingOptions) -> Self {
+        self.indexing_options = indexing_options;
+        self
+    }
+
+    /// Sets how directories are traversed when indexing, and which files of a directory are searched
+    pub fn traversal_options(mut self, traversal_options: TraversalOptions) -> Self {
+        self.traversal_options = traversal_options;
+        self
+    }
+
+    /// Sets the default search mode, result limit and sort order
+    pub fn search_options(mut self, search_options: SearchOptions) -> Self {
+        self.search_options = search_options;
+        self
+    }
+
+    /// Sets the default maximum number of matched pages returned by a search
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.search_options.limit = limit;
+        self
+    }
+
+    /// Writes the logs of the handle to the console and to the cache directory, as set up by `setup_logging` (no logger is installed by default)
+    pub fn logging(mut self, log_level: Option<String>, json_logs: bool) -> Self {
+        self.logging = true;
+        self.log_level = log_level;
+        self.json_logs = json_logs;
+        self
+    }
+
+    /// Creates the cache directory (if it doesn't exist) and opens the index
+    ///
+    /// ## Returns
+    /// - `PdfSeeker` handle owning the open index
+    pub fn build(self) -> Result<PdfSeeker, PdfSeekerError> {
+        let cache_dir: String = create_cache_dir_if_not_exists(&self.cache_dir.as_ref().map(path_string))?;
+        let index_path: String = format!("{}/index_dir", &cache_dir);
+        let track_path: String = format!("{}/track_dir", &cache_dir);
+        let track_fail_file: String = format!("{}/_FAIL.txt", &track_path);
+
+        if self.logging {
+            crate::setup_logging(Some(cache_dir.clone()), self.log_level.clone(), self.json_logs)?;
+        }
+
+        create_track_dir_if_not_exists(track_path)?;
+
+        let index: Index = create_or_open_index_with_analyzer(&index_path, self.indexing_options.analyzer.as_ref())?;
+
+        // Searches see the index as of the last change made through this handle
+        let reader: IndexReader = match index.reader_builder().reload_policy(ReloadPolicy::Manual).try_into() {
+            Ok(s) => s,
+            Err(e) => return Err(IndexingError::IndexReaderCreateError(e).into())
+        };
+
+        info!(target:"info_logging", "Index opened successfully at `{}`.", &index_path);
+        debug!(target:"other_logging", "PdfSeeker configuration -> {:?}", &self);
+
+        Ok(PdfSeeker {
+            cache_dir,
+            track_fail_file,
+            index,
+            reader,
+            writer: None,
+            indexing_options: self.indexing_options,
+            traversal_options: self.traversal_options,
+            search_options: self.search_options,
+        })
+    }
+}
+
+/// Handle owning an open index, for indexing, searching and removing PDF files
+///
+/// Unlike the free functions of the crate, which open the index on every call, a handle keeps
+/// its index reader (and its index writer, once a file was indexed or removed) open, so that
+/// long-running services can reuse it. As an index can only have one writer at a time, other
+/// processes can't write to the index while a handle holding the writer is alive.
+pub struct PdfSeeker {
+    cache_dir: String,
+    track_fail_file: String,
+    index: Index,
+    reader: IndexReader,
+    writer: Option<IndexWriter>,
+    indexing_options: IndexingOptions,
+    traversal_options: TraversalOptions,
+    search_options: SearchOptions,
+}
+
+impl PdfSeeker {
+    /// Creates a builder for configuring a handle
+    pub fn builder() -> PdfSeekerBuilder {
+        PdfSeekerBuilder::new()
+    }
+
+    /// Gets the directory where indexed files, log files and tracker files are stored
+    pub fn cache_dir(&self) -> &Path {
+        Path::new(&self.cache_dir)
+    }
+
+    /// Gets the underlying Tantivy index
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Gets the index writer, creating it on first use
+    fn writer(&mut self) -> Result<&mut IndexWriter, PdfSeekerError> {
+        let index_writer: IndexWriter = match self.writer.take() {
+            Some(s) => s,
+            None => {
+                debug!(target:"other_logging", "Creating index writer for `{}`.", &self.cache_dir);
+                create_index_writer(&self.index, &self.indexing_options)?
+            }
+        };
+
+        Ok(self.writer.insert(index_writer))
+    }
+
+    /// Makes the committed changes visible to the following searches
+    fn reload(&self) -> Result<(), PdfSeekerError> {
+        match self.reader.reload() {
+            Ok(_) => Ok(()),
+            Err(e) => Err(IndexingError::IndexReaderCreateError(e).into())
+        }
+    }
+
+    /// Indexes a PDF file, the PDF files of a directory, or the PDF files matching a glob pattern
+    ///
+    /// ## Input Parameters
+    /// - `path` defines the PDF file, directory or glob pattern (e.g. `reports/2024-*.pdf`)
+    ///
+    /// ## Returns
+    /// - Vector of PDF files with the outcome of their indexing operation
+    pub fn index_path<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<(String, IndexingStatus)>, PdfSeekerError> {
+        self.index_paths(&[path])
+    }
+
+    /// Indexes several PDF files, directories or glob patterns
+    ///
+    /// ## Input Parameters
+    /// - `paths` defines the PDF files, directories or glob patterns
+    ///
+    /// ## Returns
+    /// - Vector of PDF files with the outcome of their indexing operation
+    pub fn index_paths<P: AsRef<Path>>(&mut self, paths: &[P]) -> Result<Vec<(String, IndexingStatus)>, PdfSeekerError> {
+        let inputs: Vec<String> = paths.iter().map(path_string).collect();
+
+        write_to_file(&self.track_fail_file, &String::from(""))?;
+        let (files_list, ignored_list) = resolve_input_paths(&inputs, &self.traversal_options, Some(&self.track_fail_file))?;
+        info!(target:"info_logging", "Resolved {} PDF file(s) from {} input path(s).", files_list.len(), inputs.len());
+
+        let (index, indexing_options, track_fail_file) = (self.index.clone(), self.indexing_options.clone(), self.track_fail_file.clone());
+        let statuses: Vec<(String, IndexingStatus)> = index_files_with_writer(&files_list, &index, self.writer()?, &indexing_options, Some(&track_fail_file))?;
+        self.reload()?;
+
+        crate::log_indexing_summary(&statuses, &ignored_list);
+
+        Ok(statuses)
+    }
+
+    /// Searches a keyword in all indexed PDF files, using the default search options of the handle
+    ///
+    /// ## Input Parameters
+    /// - `search_term` defines the keyword to be searched in PDF documents
+    ///
+    /// ## Returns
+    /// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+    pub fn search(&self, search_term: &str) -> Result<PDFSearchResults, PdfSeekerError> {
+        self.search_query::<&Path>(&SearchQuery::parse(search_term), &[], &self.search_options)
+    }
+
+    /// Searches a keyword in the indexed PDF files covered by the given files, directories or glob patterns
+    ///
+    /// ## Input Parameters
+    /// - `search_term` defines the keyword to be searched in PDF documents
+    /// - `paths` defines the PDF files, directories or glob patterns to be searched
+    ///
+    /// ## Returns
+    /// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+    pub fn search_in<P: AsRef<Path>>(&self, search_term: &str, paths: &[P]) -> Result<PDFSearchResults, PdfSeekerError> {
+        self.search_query(&SearchQuery::parse(search_term), paths, &self.search_options)
+    }
+
+    /// Searches a typed query in the indexed PDF files covered by the given paths, using the given search options
+    ///
+    /// ## Input Parameters
+    /// - `query` defines the query to be searched in PDF documents
+    /// - `paths` defines the PDF files, directories or glob patterns to be searched (all indexed PDF files if empty)
+    /// - `search_options` defines how single words are matched and which page of results is returned
+    ///
+    /// ## Returns
+    /// - `PDFSearchResults` struct containing the metadata of matched PDF files and the total number of matched pages
+    pub fn search_query<P: AsRef<Path>>(&self, query: &SearchQuery, paths: &[P], search_options: &SearchOptions) -> Result<PDFSearchResults, PdfSeekerError> {
+        let path_scopes: Vec<PathScope> = paths
+            .iter()
+            .map(|s| PathScope::new(&path_string(s), &self.traversal_options))
+            .collect::<Result<Vec<PathScope>, _>>()?;
+        let path_scopes: Option<&[PathScope]> = if path_scopes.is_empty() { None } else { Some(&path_scopes) };
+
+        let search_results: SearchResults = search_query_with_searcher(&self.index, &self.reader.searcher(), query, search_options, path_scopes)?;
+        info!(target:"info_logging", "Retrieved {} of {} matched pages successfully for `{:?}` search query.", search_results.page_hits.len(), search_results.total_hits, query);
+
+        Ok(collect_documents(search_results, search_options.sort_by))
+    }
+
+    /// Removes the indexed PDF files covered by a PDF file, directory or glob pattern from the index
+    ///
+    /// The paths are matched against the index, so PDF files don't need to exist on disk.
+    ///
+    /// ## Input Parameters
+    /// - `path` defines the PDF file, directory or glob pattern
+    ///
+    /// ## Returns
+    /// - Vector of PDF file paths removed from the index
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<String>, PdfSeekerError> {
+        let path_scope: PathScope = PathScope::new(&path_string(path), &self.traversal_options)?;
+        let removed_files: Vec<String> = self.indexed_files()?
+            .into_iter()
+            .filter(|s| path_scope.contains(s))
+            .collect();
+
+        self.remove_files(&removed_files)?;
+        Ok(removed_files)
+    }
+
+    /// Removes index entries of PDF files which were deleted or moved on disk
+    ///
+    /// ## Returns
+    /// - Vector of PDF file paths removed from the index
+    pub fn sync(&mut self) -> Result<Vec<String>, PdfSeekerError> {
+        let missing_files: Vec<String> = get_missing_files(&self.reader.searcher())?;
+
+        self.remove_files(&missing_files)?;
+        Ok(missing_files)
+    }
+
+    /// Removes the index documents of the given indexed PDF files
+    fn remove_files(&mut self, pdf_files: &[String]) -> Result<(), PdfSeekerError> {
+        if pdf_files.is_empty() {
+            return Ok(());
+        }
+
+        let index: Index = self.index.clone();
+        remove_from_index_with_writer(pdf_files, &index, self.writer()?)?;
+        self.reload()?;
+
+        for file in pdf_files {
+            info!(target:"info_logging", "{} - Removed from index.", file);
+        }
+        Ok(())
+    }
+
+    /// Lists the PDF files currently present in the index
+    ///
+    /// ## Returns
+    /// - Sorted vector of indexed PDF file paths
+    pub fn indexed_files(&self) -> Result<Vec<String>, PdfSeekerError> {
+        Ok(get_indexed_files(&self.reader.searcher())?)
+    }
+
+    /// Counts the indexed PDF files, unique contents and pages
+    ///
+    /// ## Returns
+    /// - `IndexStats` struct containing the size of the index
+    pub fn stats(&self) -> Result<IndexStats, PdfSeekerError> {
+        Ok(get_index_stats(&self.reader.searcher())?)
+    }
+
+    /// Reads the text stored in the index for a page of an indexed PDF file
+    ///
+    /// ## Input Parameters
+    /// - `path` defines the indexed PDF file path (as listed by `indexed_files`)
+    /// - `page_num` defines the page number (starting at 1)
+    ///
+    /// ## Returns
+    /// - Text of the page, or None if the PDF file is not indexed or has no such page
+    pub fn page_text<P: AsRef<Path>>(&self, path: P, page_num: u32) -> Result<Option<String>, PdfSeekerError> {
+        Ok(get_page_text(&path_string(path), page_num, &self.reader.searcher())?)
+    }
+
+    /// Releases the index writer (if any), waiting for its background segment merges to complete
+    ///
+    /// Dropping a handle releases the writer as well, but abandons merges still in progress.
+    pub fn close(mut self) -> Result<(), PdfSeekerError> {
+        if let Some(s) = self.writer.take() {
+            if let Err(e) = s.wait_merging_threads() {
+                return Err(IndexingError::IndexSegmentMergeError(e).into());
+            }
+        }
+
+        Ok(())
+    }
+}
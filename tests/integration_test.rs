@@ -1,22 +1,190 @@
-use pdf_seekers;
-
 #[test]
 fn indexing_check() {
-    let file_or_directory: String = String::from("data/yolo.pdf");
+    let inputs: Vec<String> = vec![String::from("data/fast_rcnn.pdf")];
     
-    match pdf_seekers::indexing_contents(file_or_directory, None, None) {
-        Ok(_) => assert!(true),
-        Err(_) => assert!(false, "Indexing process should execute successfully")
+    match pdf_seekers::indexing_contents(inputs, None, None, None) {
+        Ok(_) => {},
+        Err(_) => panic!("Indexing process should execute successfully")
     };
 }
 
 #[test]
 fn keyword_search_check() {
-    let file_or_directory: String = String::from("data");
+    let inputs: Vec<String> = vec![String::from("data")];
     let search_term: String = String::from("convolutional");
 
-    match pdf_seekers::search_term_in_file(file_or_directory, search_term, None, None) {
-        Ok(_) => assert!(true),
-        Err(_) => assert!(false, "Searching process should execute successfully")
+    match pdf_seekers::search_term_in_file(inputs, search_term, None, None, None) {
+        Ok(_) => {},
+        Err(_) => panic!("Searching process should execute successfully")
     };
-}
\ No newline at end of file
+}
+
+#[test]
+fn recoverable_error_check() {
+    use pdf_seekers::error::{FileOperationsError, LoggingError, PdfSeekerError};
+    use std::error::Error;
+
+    let inputs: Vec<String> = vec![String::from("data/missing.pdf")];
+    let result = pdf_seekers::indexing_contents(inputs, None, None, None);
+    assert!(matches!(result, Err(PdfSeekerError::FileOperations(FileOperationsError::PathNotFoundError(_)))), "Missing inputs should be returned as errors");
+
+    let result = pdf_seekers::setup_logging(None, Some(String::from("verbose")), false);
+    assert!(matches!(result, Err(PdfSeekerError::Logging(LoggingError::InvalidLogLevel(_)))), "Invalid log levels should be returned as errors");
+
+    let cache_dir = std::env::temp_dir().join(format!("pdf_seekers_recoverable_error_check_{}", std::process::id()));
+    let result = pdf_seekers::seeker::PdfSeeker::builder().cache_dir(&cache_dir).logging(Some(String::from("verbose")), false).build();
+    assert!(matches!(result, Err(PdfSeekerError::Logging(LoggingError::InvalidLogLevel(_)))), "Invalid log levels of a handle should be returned as errors");
+
+    // The underlying cause of a failed operation is kept
+    let inputs: Vec<String> = vec![String::from("data/fast_rcnn.pdf")];
+    let search_options = pdf_seekers::search_operations::SearchOptions { limit: 0, ..Default::default() };
+    let err = pdf_seekers::search_term_in_file(inputs, String::from("convolutional"), None, None, Some(search_options)).unwrap_err();
+    assert!(err.to_string().starts_with("[SE0006_InvalidSearchOptions]"));
+    assert_eq!(err.code(), "SE0006_InvalidSearchOptions");
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn seeker_handle_check() {
+    use pdf_seekers::index_operations::IndexingStatus;
+    use pdf_seekers::seeker::PdfSeeker;
+    use std::path::Path;
+
+    let cache_dir = std::env::temp_dir().join(format!("pdf_seekers_seeker_handle_check_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let directory = cache_dir.join("files");
+    std::fs::create_dir_all(directory.join("copies")).unwrap();
+    std::fs::copy("data/fast_rcnn.pdf", directory.join("paper.pdf")).unwrap();
+    std::fs::copy("data/fast_rcnn.pdf", directory.join("copies/paper.pdf")).unwrap();
+
+    let mut seeker = PdfSeeker::builder().cache_dir(&cache_dir).limit(3).build().unwrap();
+
+    // The same handle indexes and searches, seeing its own changes
+    let statuses = seeker.index_path(&directory).unwrap();
+    assert_eq!(statuses.iter().map(|(_, s)| *s).collect::<Vec<_>>(), vec![IndexingStatus::Indexed, IndexingStatus::Duplicate]);
+    let search_results = seeker.search("convolutional").unwrap();
+    assert_eq!(search_results.documents.len(), 1);
+    assert_eq!(search_results.documents[0].matched_page_nums.len(), 3);
+
+    let stats = seeker.stats().unwrap();
+    assert_eq!((stats.num_files, stats.num_contents), (2, 1));
+    assert!(stats.num_pages > 0);
+
+    // Stored page texts are read back for every location of the content
+    let indexed_files = seeker.indexed_files().unwrap();
+    assert!(seeker.page_text(&indexed_files[0], 1).unwrap().unwrap().contains("Fast R-CNN"));
+    assert_eq!(seeker.page_text(&indexed_files[1], 1).unwrap(), seeker.page_text(&indexed_files[0], 1).unwrap());
+    assert!(seeker.page_text(&indexed_files[0], 99).unwrap().is_none());
+
+    // Removing a directory keeps the pages referenced by the other copy
+    let removed_files = seeker.remove(directory.join("copies")).unwrap();
+    assert_eq!(removed_files.len(), 1);
+    let search_results = seeker.search_in("convolutional", &[&directory]).unwrap();
+    assert_eq!(search_results.documents[0].locations.len(), 1);
+    assert_eq!(seeker.stats().unwrap().num_files, 1);
+
+    std::fs::remove_file(directory.join("paper.pdf")).unwrap();
+    assert_eq!(seeker.sync().unwrap().len(), 1);
+    assert!(seeker.search("convolutional").unwrap().documents.is_empty());
+    assert!(seeker.indexed_files().unwrap().is_empty());
+    seeker.close().unwrap();
+
+    // A new handle can write to the index once the previous one is closed
+    let mut seeker = PdfSeeker::builder().cache_dir(&cache_dir).build().unwrap();
+    assert!(seeker.index_path(Path::new("data/fast_rcnn.pdf")).is_ok());
+}
+
+#[test]
+fn repeated_logging_setup_check() {
+    let cache_dir = std::env::temp_dir().join(format!("pdf_seekers_repeated_logging_setup_check_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let cache_path: Option<String> = Some(cache_dir.display().to_string());
+
+    // Logging can be set up again in the same process, e.g. with another verbosity level
+    assert!(pdf_seekers::setup_logging(cache_path.clone(), None, false).is_ok());
+    assert!(pdf_seekers::setup_logging(cache_path.clone(), Some(String::from("debug")), false).is_ok());
+
+    let inputs: Vec<String> = vec![String::from("data/fast_rcnn.pdf")];
+    assert!(pdf_seekers::search_term_in_file(inputs.clone(), String::from("convolutional"), None, None, None).is_ok());
+    assert!(pdf_seekers::search_term_in_file(inputs.clone(), String::from("detection"), None, None, None).is_ok());
+
+    // Spans of indexing runs, indexed files and searches are written as JSON lines
+    assert!(pdf_seekers::setup_logging(cache_path.clone(), Some(String::from("info")), true).is_ok());
+    assert!(pdf_seekers::indexing_contents(inputs.clone(), cache_path.clone(), None, None).is_ok());
+    assert!(pdf_seekers::search_term_in_file(inputs, String::from("convolutional"), cache_path.clone(), None, None).is_ok());
+    assert!(pdf_seekers::setup_logging(cache_path, Some(String::from("warn")), false).is_ok());
+
+    let json_log_file = std::fs::read_dir(cache_dir.join("logs_dir")).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .unwrap();
+    let spans: Vec<serde_json::Value> = std::fs::read_to_string(json_log_file).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let find_span = |name: &str| spans.iter().find(|s| s["span"]["name"] == name).unwrap_or_else(|| panic!("missing `{}` span", name));
+
+    let file_span = find_span("index_file");
+    assert_eq!(file_span["span"]["path"], "data/fast_rcnn.pdf");
+    assert_eq!(file_span["span"]["status"], "Indexed");
+    assert_eq!(file_span["span"]["pages"], 9);
+    assert_eq!(file_span["spans"][0]["name"], "index_run");
+    assert!(find_span("index_run")["span"]["duration_ms"].is_u64());
+    assert!(find_span("search")["span"]["hits"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn sync_from_other_directory_check() {
+    let cache_dir = std::env::temp_dir().join(format!("pdf_seekers_sync_from_other_directory_check_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let cache_path: String = cache_dir.display().to_string();
+    let cli = || std::process::Command::new(env!("CARGO_BIN_EXE_pdf_seekers"));
+
+    // Index a relative path from the repository directory
+    let status = cli().args(["index", "-f", "data/fast_rcnn.pdf", "-c", &cache_path, "-l", "off"]).status().unwrap();
+    assert!(status.success());
+
+    // Syncing from another working directory keeps the file, which still exists relative to the indexing directory
+    let output = cli().args(["sync", "-c", &cache_path, "-l", "off"]).current_dir(std::env::temp_dir()).output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).is_empty());
+
+    let output = cli().args(["list", "-c", &cache_path, "-l", "off"]).current_dir(std::env::temp_dir()).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "data/fast_rcnn.pdf");
+}
+
+#[test]
+fn cli_exit_code_check() {
+    let cache_dir = std::env::temp_dir().join(format!("pdf_seekers_cli_exit_code_check_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let cache_path: String = cache_dir.display().to_string();
+    let cli = || std::process::Command::new(env!("CARGO_BIN_EXE_pdf_seekers"));
+
+    // A missing input path is a file I/O error
+    let output = cli().args(["index", "-f", "data/missing.pdf", "-c", &cache_path]).output().unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("[FO0012_PathNotFoundError]"));
+
+    let status = cli().args(["index", "-f", "data/fast_rcnn.pdf", "-c", &cache_path]).status().unwrap();
+    assert!(status.success());
+
+    // Searching without input paths covers the whole index, and logs don't end up in the results
+    let output = cli().args(["search", "-s", "convolutional", "-c", &cache_path]).output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Document Name: data/fast_rcnn.pdf"));
+    assert!(!stdout.contains(" | INFO "));
+    assert!(String::from_utf8_lossy(&output.stderr).contains(" | INFO "));
+
+    // Unknown pages and paths are reported with the not found status
+    let output = cli().args(["show", "data/fast_rcnn.pdf", "-p", "1000", "-c", &cache_path]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = cli().args(["remove", "data/missing.pdf", "-c", &cache_path]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let output = cli().args(["list", "-c", &cache_path]).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "data/fast_rcnn.pdf");
+}
@@ -1,22 +1,33 @@
-use pdf_seekers;
+use pdf_seekers::analyzer_operations::AnalyzerOptions;
+use pdf_seekers::error::{FileOperationsError, IndexingError, SearchingError};
+use pdf_seekers::file_operations::{DocumentInfo, FileFingerprint, PathScope, TraversalOptions};
+use pdf_seekers::index_operations::{IndexingOptions, IndexingStatus, PdfContents};
+use pdf_seekers::search_operations::{QueryBuilder, SearchMode, SearchOptions, SearchQuery, SortBy};
+
+/// Creates a fresh, empty directory for storing index files of a single test
+fn test_index_path(test_name: &str) -> String {
+    let index_path = std::env::temp_dir().join(format!("pdf_seekers_{}_{}", test_name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&index_path);
+    index_path.display().to_string()
+}
 
 #[test]
 fn valid_pdf_file_check() {
-    let pdf_file: String = String::from("data/yolo.pdf");
-    match pdf_seekers::file_operations::read_pdf(&pdf_file, None) {
-        Ok(_) => assert!(true),
-        Err(_) => assert!(false, "Process should be able to read the file successfully")
+    let pdf_file: String = String::from("data/fast_rcnn.pdf");
+    match pdf_seekers::file_operations::read_pdf(&pdf_file, false, None) {
+        Ok(_) => {},
+        Err(_) => panic!("Process should be able to read the file successfully")
     };
 }
 
 #[test]
 fn invalid_directory_check() {
     let directory: String = String::from("invalid_dir");
-    match pdf_seekers::file_operations::get_files_in_directory(&directory, None) {
-        Ok(_) => assert!(false, "Process should fail with FileOperationsError::DirectoryReadError"),
+    match pdf_seekers::file_operations::get_files_in_directory(&directory, &TraversalOptions::default(), None) {
+        Ok(_) => panic!("Process should fail with FileOperationsError::DirectoryReadError"),
         Err(e) => match e {
-            pdf_seekers::error::FileOperationsError::DirectoryReadError(_, _) => assert!(true),
-            _ => assert!(false, "Process should fail with FileOperationsError::DirectoryReadError"),
+            pdf_seekers::error::FileOperationsError::DirectoryReadError(_, _) => {},
+            _ => panic!("Process should fail with FileOperationsError::DirectoryReadError"),
         }
     };
 }
@@ -24,8 +35,801 @@ fn invalid_directory_check() {
 #[test]
 fn valid_directory_check() {
     let directory: String = String::from("data");
-    match pdf_seekers::file_operations::get_files_in_directory(&directory, None) {
-        Ok(_) => assert!(true),
-        Err(_) => assert!(false, "Process should be able to read the directory contents successfully")
+    match pdf_seekers::file_operations::get_files_in_directory(&directory, &TraversalOptions::default(), None) {
+        Ok(_) => {},
+        Err(_) => panic!("Process should be able to read the directory contents successfully")
+    };
+}
+
+#[test]
+fn recursive_directory_traversal_check() {
+    let directory: String = test_index_path("recursive_directory_traversal_check");
+    for sub_dir in ["projects/alpha/drafts", "projects/beta"] {
+        std::fs::create_dir_all(std::path::Path::new(&directory).join(sub_dir)).unwrap();
+    }
+    for file in ["top.pdf", "projects/alpha/report.pdf", "projects/alpha/drafts/draft.pdf", "projects/beta/summary.PDF"] {
+        std::fs::write(std::path::Path::new(&directory).join(file), b"%PDF-1.4").unwrap();
+    }
+    std::fs::write(std::path::Path::new(&directory).join("notes.txt"), b"meeting notes").unwrap();
+    let relative = |files: Vec<String>| -> Vec<String> {
+        files.iter().map(|f| f[directory.len() + 1..].replace('\\', "/")).collect()
+    };
+
+    let (files, _) = pdf_seekers::file_operations::get_files_in_directory(&directory, &TraversalOptions::default(), None).unwrap();
+    assert_eq!(relative(files), vec!["projects/alpha/drafts/draft.pdf", "projects/alpha/report.pdf", "projects/beta/summary.PDF", "top.pdf"]);
+
+    let options = TraversalOptions { max_depth: Some(1), ..TraversalOptions::default() };
+    let (files, _) = pdf_seekers::file_operations::get_files_in_directory(&directory, &options, None).unwrap();
+    assert_eq!(relative(files), vec!["top.pdf"], "Depth 1 should only descend into direct subdirectories");
+
+    let options = TraversalOptions {
+        include: vec![String::from("projects/**")],
+        exclude: vec![String::from("**/drafts/**")],
+        ..TraversalOptions::default()
+    };
+    let (files, _) = pdf_seekers::file_operations::get_files_in_directory(&directory, &options, None).unwrap();
+    assert_eq!(relative(files), vec!["projects/alpha/report.pdf", "projects/beta/summary.PDF"]);
+}
+
+#[test]
+fn pdf_detection_check() {
+    let directory: String = test_index_path("pdf_detection_check");
+    std::fs::create_dir_all(std::path::Path::new(&directory).join("v1.2")).unwrap();
+    std::fs::write(std::path::Path::new(&directory).join("v1.2/README"), b"%PDF-1.7\n").unwrap();
+    std::fs::write(std::path::Path::new(&directory).join("v1.2/fake.pdf"), b"<html></html>").unwrap();
+    let versioned_dir: String = format!("{}/v1.2", &directory);
+
+    assert!(pdf_seekers::file_operations::check_if_directory(&versioned_dir).unwrap(), "Directory names containing a dot are still directories");
+    assert!(!pdf_seekers::file_operations::check_if_directory(&format!("{}/README", &versioned_dir)).unwrap());
+    assert!(matches!(
+        pdf_seekers::file_operations::check_if_directory(&format!("{}/missing.pdf", &versioned_dir)),
+        Err(FileOperationsError::PathNotFoundError(_))
+    ));
+
+    let (files, _) = pdf_seekers::file_operations::get_files_in_directory(&versioned_dir, &TraversalOptions::default(), None).unwrap();
+    assert_eq!(files, vec![format!("{}/README", &versioned_dir)], "PDFs should be detected by their header rather than their extension");
+}
+
+#[test]
+fn seekerignore_rules_check() {
+    let directory: String = test_index_path("seekerignore_rules_check");
+    for sub_dir in ["archive/scans", "team/private"] {
+        std::fs::create_dir_all(std::path::Path::new(&directory).join(sub_dir)).unwrap();
+    }
+    for file in ["report.pdf", "archive/scans/scan.pdf", "archive/keep.pdf", "team/notes.pdf", "team/secret.pdf", "team/private/plan.pdf"] {
+        std::fs::write(std::path::Path::new(&directory).join(file), b"%PDF-1.4").unwrap();
+    }
+    std::fs::write(std::path::Path::new(&directory).join("secret.txt"), b"not a PDF").unwrap();
+    std::fs::write(std::path::Path::new(&directory).join(".seekerignore"), "archive/scans/\n*secret*\n").unwrap();
+    std::fs::write(std::path::Path::new(&directory).join("team/.seekerignore"), "private\n!secret.pdf\n").unwrap();
+    let relative = |files: Vec<String>| -> Vec<String> {
+        files.iter().map(|f| f[directory.len() + 1..].replace('\\', "/")).collect()
     };
-}
\ No newline at end of file
+
+    let (files, ignored) = pdf_seekers::file_operations::get_files_in_directory(&directory, &TraversalOptions::default(), None).unwrap();
+    assert_eq!(relative(files), vec!["archive/keep.pdf", "report.pdf", "team/notes.pdf", "team/secret.pdf"], "Nested ignore files should be able to re-include paths");
+    assert_eq!(relative(ignored), vec!["archive/scans", "secret.txt", "team/private"], "Ignored directories should be reported once, without their contents, and ignored files before being opened");
+
+    // Ignore files of parent directories also apply to directory, glob and explicit file inputs
+    let inputs: Vec<String> = ["archive/scans", "team/*", "archive/scans/scan.pdf", "team/secret.pdf", "archive/keep.pdf"]
+        .iter()
+        .map(|s| format!("{}/{}", &directory, s))
+        .collect();
+    let (files, ignored) = pdf_seekers::file_operations::resolve_input_paths(&inputs, &TraversalOptions::default(), None).unwrap();
+    assert_eq!(relative(files), vec!["team/notes.pdf", "team/secret.pdf", "archive/keep.pdf"]);
+    assert_eq!(relative(ignored), vec!["archive/scans", "team/private", "archive/scans/scan.pdf"]);
+}
+
+#[test]
+fn file_documents_excluded_check() {
+    let index_path: String = test_index_path("file_documents_excluded_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let contents = PdfContents { pdf_page_nums: vec![1, 2], pdf_texts: vec![String::from("object detection"), String::from("appendix")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    // Queries matching the location documents of PDF files only return pages
+    for query in ["*", "path:paper.pdf", "doc_kind:file"] {
+        let search_results = pdf_seekers::search_operations::search_keyword_with_options(&index, query, &SearchOptions::default()).unwrap();
+        assert!(search_results.page_hits.iter().all(|hit| hit.page_num > 0 && hit.doc_name == "paper.pdf"), "`{}` should only match pages", query);
+        assert_eq!(search_results.total_hits, search_results.page_hits.len());
+    }
+    assert_eq!(pdf_seekers::search_operations::search_keyword(&index, "*").unwrap().len(), 2);
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "doc_kind:file").unwrap().is_empty());
+}
+
+#[test]
+fn multiple_input_paths_check() {
+    let directory: String = test_index_path("multiple_input_paths_check");
+    for sub_dir in ["reports", "papers"] {
+        std::fs::create_dir_all(std::path::Path::new(&directory).join(sub_dir)).unwrap();
+    }
+    for file in ["reports/2023-q4.pdf", "reports/2024-q1.pdf", "reports/2024-q2.pdf", "papers/paper.pdf"] {
+        std::fs::write(std::path::Path::new(&directory).join(file), b"%PDF-1.4").unwrap();
+    }
+
+    let list_file: String = format!("{}/inputs.txt", &directory);
+    std::fs::write(&list_file, format!("# papers\n{0}/papers\n\n{0}/reports/2024-*.pdf\n", &directory)).unwrap();
+    let mut inputs: Vec<String> = pdf_seekers::file_operations::read_path_list(&list_file).unwrap();
+    inputs.push(format!("{}/papers/paper.pdf", &directory));
+    assert_eq!(inputs.len(), 3, "Blank lines and comments should be skipped");
+
+    let (files, _) = pdf_seekers::file_operations::resolve_input_paths(&inputs, &TraversalOptions::default(), None).unwrap();
+    assert_eq!(files, vec![
+        format!("{}/papers/paper.pdf", &directory),
+        format!("{}/reports/2024-q1.pdf", &directory),
+        format!("{}/reports/2024-q2.pdf", &directory),
+    ], "Inputs should be expanded and de-duplicated in order");
+
+    let inputs: Vec<String> = vec![format!("{}/missing", &directory)];
+    assert!(matches!(
+        pdf_seekers::file_operations::resolve_input_paths(&inputs, &TraversalOptions::default(), None),
+        Err(FileOperationsError::PathNotFoundError(_))
+    ));
+}
+
+#[test]
+fn per_page_search_check() {
+    let index_path: String = test_index_path("per_page_search_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let contents = PdfContents {
+        pdf_page_nums: vec![1, 2, 3],
+        pdf_texts: vec![
+            String::from("introduction to object detection"),
+            String::from("region proposal networks share convolutional features"),
+            String::from("convolutional layers convolutional filters"),
+        ],
+        ..PdfContents::default()
+    };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "convolutional").unwrap();
+    let matched_pages: Vec<u32> = page_hits.iter().map(|hit| hit.page_num).collect();
+
+    assert_eq!(matched_pages, vec![3, 2], "Only the matching pages should be returned, ranked by score");
+    assert!(page_hits.iter().all(|hit| hit.doc_name == "paper.pdf"));
+    assert!(page_hits[0].score > page_hits[1].score);
+}
+
+#[test]
+fn search_pagination_check() {
+    let index_path: String = test_index_path("search_pagination_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let pdf_page_nums: Vec<u32> = (1..=15).collect();
+    let pdf_texts: Vec<String> = pdf_page_nums.iter().map(|s| format!("network page {}", s)).collect();
+    let contents = PdfContents { pdf_page_nums, pdf_texts, ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    // Results past the default limit are counted, and can be paged through
+    let search_results = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &SearchOptions::default()).unwrap();
+    assert_eq!((search_results.page_hits.len(), search_results.total_hits), (10, 15));
+
+    let mut page_nums: Vec<u32> = Vec::new();
+    for offset in [0, 6, 12] {
+        let search_options = SearchOptions { limit: 6, offset, ..SearchOptions::default() };
+        let search_results = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &search_options).unwrap();
+        assert_eq!(search_results.total_hits, 15);
+        page_nums.extend(search_results.page_hits.iter().map(|s| s.page_num));
+    }
+    page_nums.sort();
+    assert_eq!(page_nums, (1..=15).collect::<Vec<u32>>(), "Every matched page should be returned exactly once");
+
+    let result = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &SearchOptions { limit: 0, ..SearchOptions::default() });
+    assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "A zero limit should be rejected");
+
+    for (limit, offset) in [(usize::MAX, 1), (1, usize::MAX), (pdf_seekers::search_operations::MAX_SEARCH_WINDOW, 1)] {
+        let result = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &SearchOptions { limit, offset, ..SearchOptions::default() });
+        assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "An oversized result window should be rejected");
+    }
+
+    // Large windows within the bound are clamped to the indexed pages
+    let search_options = SearchOptions { limit: 5_000, offset: 4_000, ..SearchOptions::default() };
+    let search_results = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &search_options).unwrap();
+    assert_eq!((search_results.page_hits.len(), search_results.total_hits), (0, 15));
+    let search_options = SearchOptions { limit: 5_000, ..SearchOptions::default() };
+    assert_eq!(pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &search_options).unwrap().page_hits.len(), 15);
+}
+
+#[test]
+fn path_scoped_search_check() {
+    let index_path: String = test_index_path("path_scoped_search_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    // The pages in `other/` score higher than the ones in `reports/`, and `shared.pdf` is found in both
+    let files = [
+        ("./other/best.pdf", "o1", "network network network"),
+        ("other/better.pdf", "o2", "network network"),
+        ("reports/a.pdf", "r1", "network and a lot of other words"),
+        ("reports/2024/b.pdf", "r2", "network and some other words"),
+        ("reports/drafts/c.pdf", "r3", "network and words"),
+        ("other/shared.pdf", "s", "network network network network"),
+        ("reports/shared.pdf", "s", "network network network network"),
+    ];
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    for (file, content_hash, text) in files {
+        let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from(text)], ..PdfContents::default() };
+        let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from(content_hash) };
+        pdf_seekers::index_operations::parse_and_index_pdf(file, &contents, &fingerprint, &mut index_writer).unwrap();
+    }
+
+    let search_scoped = |inputs: &[&str], traversal_options: &TraversalOptions, limit: usize| {
+        let path_scopes: Vec<PathScope> = inputs.iter().map(|s| PathScope::new(s, traversal_options).unwrap()).collect();
+        let search_options = SearchOptions { limit, ..SearchOptions::default() };
+        let search_results = pdf_seekers::search_operations::search_query_in_scope(&index, &SearchQuery::parse("network"), &search_options, Some(&path_scopes)).unwrap();
+        let doc_names: Vec<String> = search_results.page_hits.iter().map(|s| s.doc_name.clone()).collect();
+        (doc_names, search_results.total_hits)
+    };
+
+    // The top pages of the directory are returned, even though better pages exist elsewhere
+    let (doc_names, total_hits) = search_scoped(&["reports"], &TraversalOptions::default(), 2);
+    assert_eq!(doc_names, vec!["reports/shared.pdf", "reports/drafts/c.pdf"]);
+    assert_eq!(total_hits, 4);
+
+    let (doc_names, total_hits) = search_scoped(&["./reports/"], &TraversalOptions { max_depth: Some(0), exclude: vec![String::from("shared.pdf")], ..TraversalOptions::default() }, 10);
+    assert_eq!((doc_names, total_hits), (vec![String::from("reports/a.pdf")], 1));
+
+    let (doc_names, _) = search_scoped(&["reports"], &TraversalOptions { exclude: vec![String::from("**/drafts")], ..TraversalOptions::default() }, 10);
+    assert!(!doc_names.contains(&String::from("reports/drafts/c.pdf")), "Files below excluded directories should not be searched");
+
+    // Files and glob patterns are scoped as well, without crossing directory boundaries with `*`
+    let (doc_names, total_hits) = search_scoped(&["other/best.pdf", "reports/*/b.pdf"], &TraversalOptions::default(), 10);
+    assert_eq!((doc_names, total_hits), (vec![String::from("./other/best.pdf"), String::from("reports/2024/b.pdf")], 2));
+
+    let (doc_names, total_hits) = search_scoped(&["*/*.pdf"], &TraversalOptions::default(), 10);
+    assert_eq!((doc_names.len(), total_hits), (4, 4));
+
+    let (doc_names, total_hits) = search_scoped(&["missing"], &TraversalOptions::default(), 10);
+    assert_eq!((doc_names.len(), total_hits), (0, 0));
+}
+
+#[test]
+fn result_sorting_check() {
+    use chrono::TimeZone;
+
+    let index_path: String = test_index_path("result_sorting_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    // `a.pdf` is the least relevant, but has the most pages and the latest modification date
+    let document_info = DocumentInfo { mod_date: chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single(), ..DocumentInfo::default() };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    let contents = PdfContents {
+        pdf_page_nums: vec![1, 2, 3],
+        pdf_texts: vec![String::from("network and a lot of other words"), String::from("appendix"), String::from("references")],
+        document_info,
+        ..PdfContents::default()
+    };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    pdf_seekers::index_operations::parse_and_index_pdf("a.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1, 2], pdf_texts: vec![String::from("network network"), String::from("network")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("b") };
+    pdf_seekers::index_operations::parse_and_index_pdf("b.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "network").unwrap();
+    let mut documents: Vec<pdf_seekers::search_operations::PDFMetadata> = ["a.pdf", "b.pdf"]
+        .iter()
+        .map(|file| {
+            let hits: Vec<_> = page_hits.iter().filter(|s| s.doc_name == *file).cloned().collect();
+            pdf_seekers::search_operations::run_analysis(file, &hits)
+        })
+        .collect();
+
+    // Documents carry the score of their best page, and the scores of all their matched pages
+    let b_metadata = &documents[1];
+    assert_eq!(b_metadata.page_scores.iter().map(|s| s.0).collect::<Vec<u32>>(), vec![1, 2]);
+    assert!(b_metadata.page_scores[0].1 > b_metadata.page_scores[1].1);
+    assert_eq!(b_metadata.score, b_metadata.page_scores[0].1);
+
+    let cases = [
+        ("relevance", vec!["b.pdf", "a.pdf"]),
+        ("path", vec!["a.pdf", "b.pdf"]),
+        ("mod-date", vec!["a.pdf", "b.pdf"]),
+        ("page-count", vec!["a.pdf", "b.pdf"]),
+    ];
+    for (sort_key, expected_order) in cases {
+        let sort_by: SortBy = sort_key.parse().unwrap();
+        pdf_seekers::search_operations::sort_documents(&mut documents, sort_by);
+        assert_eq!(documents.iter().map(|s| s.doc_name.as_str()).collect::<Vec<&str>>(), expected_order, "Unexpected order when sorting by {}", sort_key);
+    }
+
+    assert!(matches!("size".parse::<SortBy>(), Err(SearchingError::InvalidSearchOptions(_))));
+
+    // Pages are selected by relevance before sorting, so `a.pdf` isn't returned with a single page
+    let search_options = SearchOptions { limit: 1, sort_by: SortBy::Path, ..SearchOptions::default() };
+    let search_results = pdf_seekers::search_operations::search_keyword_with_options(&index, "network", &search_options).unwrap();
+    let search_results = pdf_seekers::search_operations::collect_documents(search_results, SortBy::Path);
+    assert_eq!(search_results.documents.iter().map(|s| s.doc_name.as_str()).collect::<Vec<&str>>(), vec!["b.pdf"]);
+}
+
+#[test]
+fn typed_query_check() {
+    let index_path: String = test_index_path("typed_query_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let document_info = DocumentInfo { author: Some(String::from("Ross Girshick")), ..DocumentInfo::default() };
+    let contents = PdfContents {
+        pdf_page_nums: vec![1, 2, 3, 4],
+        pdf_texts: vec![
+            String::from("region proposal networks for object detection"),
+            String::from("proposal of a new region"),
+            String::from("object and region detection"),
+            String::from("networks: a+b (c OR d"),
+        ],
+        document_info,
+        ..PdfContents::default()
+    };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let search = |query: SearchQuery| -> Vec<u32> {
+        let mut page_nums: Vec<u32> = pdf_seekers::search_operations::search_query(&index, &query, &SearchOptions::default()).unwrap()
+            .page_hits
+            .iter()
+            .map(|s| s.page_num)
+            .collect();
+        page_nums.sort();
+        page_nums
+    };
+
+    // Phrases keep the order of their words unless a slop is set, while proximity queries match both orders
+    assert_eq!(search(SearchQuery::phrase("region proposal")), vec![1]);
+    assert!(search(SearchQuery::phrase("proposal region")).is_empty());
+    assert_eq!(search(SearchQuery::phrase("proposal region").with_slop(3)), vec![1, 2]);
+    assert_eq!(search(SearchQuery::near("region", "detection", 2)), vec![3]);
+    assert_eq!(search(SearchQuery::near("detection", "object", 1)), vec![1]);
+    let result = pdf_seekers::search_operations::search_query(&index, &SearchQuery::near("region proposal", "detection", 3), &SearchOptions::default());
+    assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "Proximity operands should be single words");
+
+    // Boolean clauses, field scopes and boosts
+    assert_eq!(search(QueryBuilder::new().must(SearchQuery::term("region")).must_not(SearchQuery::phrase("region proposal")).build()), vec![2, 3]);
+    assert_eq!(search(SearchQuery::term("girshick").in_field("author")), vec![1, 2, 3, 4]);
+    assert!(search(SearchQuery::term("girshick")).is_empty());
+    let query: SearchQuery = QueryBuilder::new()
+        .should(SearchQuery::term("region"))
+        .should(SearchQuery::term("object").boost(10.0))
+        .build();
+    let page_hits = pdf_seekers::search_operations::search_query(&index, &query, &SearchOptions::default()).unwrap().page_hits;
+    assert!([1, 3].contains(&page_hits[0].page_num) && [1, 3].contains(&page_hits[1].page_num), "Boosted clauses should rank first");
+
+    // Words are never read as query syntax, unlike raw query strings
+    assert!(matches!(pdf_seekers::search_operations::search_keyword(&index, "a+b (c OR d"), Err(SearchingError::QueryParserError(_))));
+    assert_eq!(search(SearchQuery::phrase("a+b (c OR d")), vec![4]);
+
+    // Snippets highlight the words of the phrase
+    let page_hits = pdf_seekers::search_operations::search_query(&index, &SearchQuery::phrase("region proposal"), &SearchOptions::default()).unwrap().page_hits;
+    assert_eq!(page_hits[0].snippet.as_ref().unwrap().render("[", "]"), "[region] [proposal] networks for object detection");
+
+    let result = pdf_seekers::search_operations::search_query(&index, &SearchQuery::term("1").in_field("page_num"), &SearchOptions::default());
+    assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "Non-text fields should be rejected");
+    let result = pdf_seekers::search_operations::search_query(&index, &SearchQuery::term("x").in_field("missing"), &SearchOptions::default());
+    assert!(matches!(result, Err(SearchingError::IndexFieldNotFound(_, _))));
+}
+
+#[test]
+fn reindex_replaces_documents_check() {
+    let index_path: String = test_index_path("reindex_replaces_documents_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("outdated draft")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let contents = PdfContents { pdf_page_nums: vec![1, 2], pdf_texts: vec![String::from("final version"), String::from("appendix")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("b") };
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "outdated").unwrap().is_empty());
+    assert_eq!(pdf_seekers::search_operations::search_keyword(&index, "final").unwrap().len(), 1);
+    assert_eq!(pdf_seekers::index_operations::get_indexed_fingerprint("paper.pdf", &index).unwrap(), Some(fingerprint));
+}
+
+#[test]
+fn schema_version_check() {
+    let index_path: String = test_index_path("schema_version_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+    drop(index);
+    assert!(pdf_seekers::index_operations::create_or_open_index(&index_path).is_ok());
+
+    // Indexes created by older versions (without a stored schema version) have to be rebuilt
+    std::fs::remove_file(format!("{}/{}", &index_path, pdf_seekers::index_operations::SCHEMA_VERSION_FILE)).unwrap();
+    let result = pdf_seekers::index_operations::create_or_open_index(&index_path);
+    assert!(matches!(result, Err(IndexingError::IndexRebuildRequired(_, 0, _))), "Unversioned indexes should be rejected");
+}
+
+#[test]
+fn unchanged_file_skipped_check() {
+    let index_path: String = test_index_path("unchanged_file_skipped_check");
+    let pdf_file: String = String::from("data/fast_rcnn.pdf");
+
+    let status = pdf_seekers::index_operations::file_indexing(&pdf_file, &index_path, None).unwrap();
+    assert_eq!(status, IndexingStatus::Indexed);
+
+    let status = pdf_seekers::index_operations::file_indexing(&pdf_file, &index_path, None).unwrap();
+    assert_eq!(status, IndexingStatus::Unchanged, "Unchanged file should not be indexed again");
+}
+
+#[test]
+fn touched_file_fingerprint_check() {
+    let index_path: String = test_index_path("touched_file_fingerprint_check");
+    let pdf_file: String = format!("{}_paper.pdf", &index_path);
+    std::fs::copy("data/fast_rcnn.pdf", &pdf_file).unwrap();
+    pdf_seekers::index_operations::file_indexing(&pdf_file, &index_path, None).unwrap();
+
+    // A new modification time with the same content is recorded without re-indexing the pages
+    let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+    std::fs::File::options().write(true).open(&pdf_file).unwrap().set_modified(modified).unwrap();
+    let status = pdf_seekers::index_operations::file_indexing(&pdf_file, &index_path, None).unwrap();
+    assert_eq!(status, IndexingStatus::Unchanged);
+
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+    let fingerprint = pdf_seekers::index_operations::get_indexed_fingerprint(&pdf_file, &index).unwrap().unwrap();
+    assert_eq!(fingerprint.modified, 1_000_000_000 * 1_000_000_000);
+    assert_eq!(pdf_seekers::index_operations::get_index_stats(&index.reader().unwrap().searcher()).unwrap().num_files, 1);
+}
+
+#[test]
+fn sync_purges_missing_files_check() {
+    let index_path: String = test_index_path("sync_purges_missing_files_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let existing_file: String = String::from("data/fast_rcnn.pdf");
+    let missing_file: String = String::from("data/deleted.pdf");
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("object detection")], ..PdfContents::default() };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    pdf_seekers::index_operations::parse_and_index_pdf(&existing_file, &contents, &fingerprint, &mut index_writer).unwrap();
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("b") };
+    pdf_seekers::index_operations::parse_and_index_pdf(&missing_file, &contents, &fingerprint, &mut index_writer).unwrap();
+    drop(index_writer);
+
+    let removed_files = pdf_seekers::index_operations::sync_index(&index).unwrap();
+    assert_eq!(removed_files, vec![missing_file.clone()]);
+    assert_eq!(pdf_seekers::index_operations::get_indexed_files(&index.reader().unwrap().searcher()).unwrap(), vec![existing_file]);
+
+    let removed_files = pdf_seekers::index_operations::sync_index(&index).unwrap();
+    assert!(removed_files.is_empty(), "Purged files should not be reported again");
+}
+
+#[test]
+fn pdf_document_info_check() {
+    let pdf_file: String = String::from("data/fast_rcnn.pdf");
+    let (_, _, _, document_info) = pdf_seekers::file_operations::read_pdf(&pdf_file, false, None).unwrap();
+
+    assert_eq!(document_info.producer.as_deref(), Some("pdfTeX-1.40.12"));
+    assert_eq!(document_info.title, None, "Empty Info entries should be ignored");
+    assert_eq!(document_info.creation_date.map(|d| d.to_rfc3339()).as_deref(), Some("2015-09-29T00:17:35+00:00"));
+}
+
+#[test]
+fn pdf_date_parsing_check() {
+    let parse = |s: &str| pdf_seekers::file_operations::parse_pdf_date(s).map(|d| d.to_rfc3339());
+
+    assert_eq!(parse("D:20230415103000+02'00'").as_deref(), Some("2023-04-15T08:30:00+00:00"));
+    assert_eq!(parse("D:2023").as_deref(), Some("2023-01-01T00:00:00+00:00"));
+    assert_eq!(parse("not a date"), None);
+}
+
+#[test]
+fn metadata_field_search_check() {
+    let index_path: String = test_index_path("metadata_field_search_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let document_info = DocumentInfo {
+        author: Some(String::from("Ross Girshick")),
+        creation_date: pdf_seekers::file_operations::parse_pdf_date("D:20230415"),
+        ..DocumentInfo::default()
+    };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("object detection")], document_info, ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    pdf_seekers::index_operations::parse_and_index_pdf("fast_rcnn.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("object detection")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("b") };
+    pdf_seekers::index_operations::parse_and_index_pdf("yolo.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "author:girshick").unwrap();
+    assert_eq!(page_hits.len(), 1);
+    assert_eq!(page_hits[0].doc_name, "fast_rcnn.pdf");
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "creation_date:[2023-01-01T00:00:00Z TO 2024-01-01T00:00:00Z]").unwrap();
+    assert_eq!(page_hits.len(), 1);
+    assert_eq!(page_hits[0].doc_name, "fast_rcnn.pdf");
+}
+
+#[test]
+fn parallel_indexing_check() {
+    let index_path: String = test_index_path("parallel_indexing_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let files: Vec<String> = vec![String::from("data/fast_rcnn.pdf"), String::from("data/missing.pdf")];
+    let options = IndexingOptions { num_workers: 2, writer_threads: 2, writer_memory_bytes: 64 << 20, commit_batch_size: 1, analyzer: None };
+
+    let mut statuses = pdf_seekers::index_operations::index_files(&files, &index, &options, None).unwrap();
+    statuses.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(statuses, vec![
+        (String::from("data/fast_rcnn.pdf"), IndexingStatus::Indexed),
+        (String::from("data/missing.pdf"), IndexingStatus::Failed),
+    ]);
+
+    let statuses = pdf_seekers::index_operations::index_files(&files[..1], &index, &options, None).unwrap();
+    assert_eq!(statuses, vec![(String::from("data/fast_rcnn.pdf"), IndexingStatus::Unchanged)]);
+    assert!(!pdf_seekers::search_operations::search_keyword(&index, "convolutional").unwrap().is_empty());
+}
+
+#[test]
+fn batch_reindex_purges_replaced_contents_check() {
+    let index_path: String = test_index_path("batch_reindex_purges_replaced_contents_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    // The stored content of `paper.pdf` is outdated, while `draft.pdf` keeps sharing its old content
+    let directory: String = format!("{}_files", &index_path);
+    std::fs::create_dir_all(&directory).unwrap();
+    let files: Vec<String> = vec![format!("{}/paper.pdf", &directory), format!("{}/slides.pdf", &directory)];
+    for file in &files {
+        std::fs::copy("data/fast_rcnn.pdf", file).unwrap();
+    }
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("outdated draft")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    pdf_seekers::index_operations::parse_and_index_pdf(&files[0], &contents, &fingerprint, &mut index_writer).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("draft.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("stale slides")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("b") };
+    pdf_seekers::index_operations::parse_and_index_pdf(&files[1], &contents, &fingerprint, &mut index_writer).unwrap();
+    drop(index_writer);
+
+    // Replaced contents are removed with the batch which stopped referencing them
+    let options = IndexingOptions { num_workers: 1, commit_batch_size: 1, ..IndexingOptions::default() };
+    let statuses = pdf_seekers::index_operations::index_files(&files, &index, &options, None).unwrap();
+    assert_eq!(statuses.iter().map(|(_, s)| *s).collect::<Vec<_>>(), vec![IndexingStatus::Reindexed, IndexingStatus::Duplicate]);
+
+    assert_eq!(pdf_seekers::search_operations::search_keyword(&index, "outdated").unwrap().len(), 1, "Contents still referenced by another file should be kept");
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "stale").unwrap().is_empty());
+    let stats = pdf_seekers::index_operations::get_index_stats(&index.reader().unwrap().searcher()).unwrap();
+    assert_eq!((stats.num_files, stats.num_contents), (3, 2));
+}
+
+#[test]
+fn duplicate_content_indexed_once_check() {
+    let index_path: String = test_index_path("duplicate_content_indexed_once_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let directory: String = format!("{}_files", &index_path);
+    std::fs::create_dir_all(&directory).unwrap();
+    let files: Vec<String> = vec![format!("{}/copy_a.pdf", &directory), format!("{}/copy_b.pdf", &directory)];
+    for file in &files {
+        std::fs::copy("data/fast_rcnn.pdf", file).unwrap();
+    }
+
+    let options = IndexingOptions { num_workers: 1, ..IndexingOptions::default() };
+    let statuses = pdf_seekers::index_operations::index_files(&files, &index, &options, None).unwrap();
+    assert_eq!(statuses.iter().map(|(_, s)| *s).collect::<Vec<_>>(), vec![IndexingStatus::Indexed, IndexingStatus::Duplicate]);
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "convolutional").unwrap();
+    let num_pages_hit: usize = page_hits.len();
+    assert!(page_hits.iter().all(|hit| hit.locations == files), "Every hit should list all locations of the document");
+    let mut matched_pages: Vec<u32> = page_hits.iter().map(|hit| hit.page_num).collect();
+    matched_pages.sort();
+    matched_pages.dedup();
+    assert_eq!(matched_pages.len(), num_pages_hit, "Identical copies should only be indexed once");
+
+    // Pages are kept as long as one location still references them
+    pdf_seekers::index_operations::remove_from_index(&files[..1], &index).unwrap();
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "convolutional").unwrap();
+    assert_eq!(page_hits.len(), num_pages_hit);
+    assert!(page_hits.iter().all(|hit| hit.locations == files[1..]));
+
+    pdf_seekers::index_operations::remove_from_index(&files[1..], &index).unwrap();
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "convolutional").unwrap().is_empty());
+}
+
+#[test]
+fn configured_analyzer_check() {
+    let index_path: String = test_index_path("configured_analyzer_check");
+    let analyzer_options = AnalyzerOptions {
+        language: Some(String::from("english")),
+        stemming: true,
+        stop_words: true,
+        ascii_folding: true,
+        ..AnalyzerOptions::default()
+    };
+    let index = pdf_seekers::index_operations::create_or_open_index_with_analyzer(&index_path, Some(&analyzer_options)).unwrap();
+
+    let contents = PdfContents { pdf_page_nums: vec![1], pdf_texts: vec![String::from("Résumé of the Neural Networks team")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("cv.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+    drop(index_writer);
+    drop(index);
+
+    // The stored analyzer is used when the index is opened again, whatever is requested
+    let index = pdf_seekers::index_operations::create_or_open_index_with_analyzer(&index_path, Some(&AnalyzerOptions::default())).unwrap();
+    assert_eq!(pdf_seekers::analyzer_operations::load_analyzer_options(&index_path).unwrap(), analyzer_options);
+    for query in ["network", "resume", "NEURAL"] {
+        assert_eq!(pdf_seekers::search_operations::search_keyword(&index, query).unwrap().len(), 1, "`{}` should match", query);
+    }
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "the").unwrap().is_empty(), "Stop words should not be indexed");
+
+    let invalid_options = AnalyzerOptions { stemming: true, ..AnalyzerOptions::default() };
+    let result = pdf_seekers::index_operations::create_or_open_index_with_analyzer(&test_index_path("invalid_analyzer_check"), Some(&invalid_options));
+    assert!(matches!(result, Err(IndexingError::InvalidAnalyzerOptions(_))), "Stemming without a language should be rejected");
+}
+
+#[test]
+fn language_detection_check() {
+    let samples = [
+        ("english", "The region proposal network shares convolutional features with the detection network."),
+        ("german", "Die Häuser in der Altstadt wurden im letzten Jahrhundert vollständig renoviert."),
+        ("french", "Les maisons de la vieille ville ont été entièrement rénovées au siècle dernier."),
+        ("chinese", "我们提出了一种基于区域的快速卷积网络目标检测方法。"),
+        ("japanese", "これは日本語の文章です。東京はとても大きな都市です。"),
+    ];
+    for (language, text) in samples {
+        assert_eq!(pdf_seekers::language_operations::detect_language(text).as_deref(), Some(language));
+    }
+
+    let (_, _, pdf_languages, _) = pdf_seekers::file_operations::read_pdf(&String::from("data/fast_rcnn.pdf"), true, None).unwrap();
+    assert_eq!(pdf_languages[0].as_deref(), Some("english"));
+    let (_, _, pdf_languages, _) = pdf_seekers::file_operations::read_pdf(&String::from("data/fast_rcnn.pdf"), false, None).unwrap();
+    assert!(pdf_languages.iter().all(|s| s.is_none()), "Languages should only be detected if enabled");
+
+    // Every page is tokenized and stemmed according to its detected language
+    let index_path: String = test_index_path("language_detection_check");
+    let analyzer_options = AnalyzerOptions { stemming: true, detect_language: true, ..AnalyzerOptions::default() };
+    let index = pdf_seekers::index_operations::create_or_open_index_with_analyzer(&index_path, Some(&analyzer_options)).unwrap();
+
+    let pdf_texts: Vec<String> = [samples[0].1, samples[1].1, samples[3].1].iter().map(|s| s.to_string()).collect();
+    let pdf_languages: Vec<Option<String>> = pdf_texts.iter().map(|s| pdf_seekers::language_operations::detect_language(s)).collect();
+    let contents = PdfContents { pdf_page_nums: vec![1, 2, 3], pdf_texts, pdf_languages, ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("reports.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    for (query, page_num) in [("networks", 1), ("haus", 2), ("检测", 3)] {
+        let page_hits = pdf_seekers::search_operations::search_keyword(&index, query).unwrap();
+        assert_eq!(page_hits.iter().map(|s| s.page_num).collect::<Vec<u32>>(), vec![page_num], "`{}` should match page {}", query, page_num);
+    }
+
+    // Pages can be filtered by their detected language
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "language:german").unwrap();
+    assert_eq!(page_hits.len(), 1);
+    assert_eq!(page_hits[0].language.as_deref(), Some("german"));
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "network AND language:german").unwrap().is_empty());
+}
+
+#[test]
+fn fuzzy_search_check() {
+    let index_path: String = test_index_path("fuzzy_search_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let contents = PdfContents { pdf_page_nums: vec![1, 2], pdf_texts: vec![String::from("deep convolutional networks"), String::from("region proposals")], ..PdfContents::default() };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    // Misspelled terms only match in fuzzy mode
+    assert!(pdf_seekers::search_operations::search_keyword(&index, "convolutonal").unwrap().is_empty());
+    let fuzzy_options = SearchOptions { mode: SearchMode::Fuzzy, ..SearchOptions::default() };
+    let page_hits = pdf_seekers::search_operations::search_keyword_with_options(&index, "convolutonal", &fuzzy_options).unwrap().page_hits;
+    assert_eq!(page_hits.iter().map(|s| s.page_num).collect::<Vec<u32>>(), vec![1]);
+
+    // Distance 1 doesn't tolerate two typos, while distance 2 does (a transposition counts as one edit)
+    assert!(pdf_seekers::search_operations::search_keyword_with_options(&index, "regn", &SearchOptions { mode: SearchMode::Fuzzy, fuzzy_distance: 1, ..SearchOptions::default() }).unwrap().page_hits.is_empty());
+    assert_eq!(pdf_seekers::search_operations::search_keyword_with_options(&index, "regn", &SearchOptions { mode: SearchMode::Fuzzy, fuzzy_distance: 2, ..SearchOptions::default() }).unwrap().page_hits.len(), 1);
+    assert_eq!(pdf_seekers::search_operations::search_keyword_with_options(&index, "regoin", &fuzzy_options).unwrap().page_hits.len(), 1);
+
+    let result = pdf_seekers::search_operations::search_keyword_with_options(&index, "region", &SearchOptions { mode: SearchMode::Fuzzy, fuzzy_distance: 3, ..SearchOptions::default() });
+    assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "Unsupported fuzzy distances should be rejected");
+
+    // Snippets highlight the word variant found in the page text
+    assert_eq!(page_hits[0].occurrences[0].term, "convolutional");
+    assert_eq!(page_hits[0].snippet.as_ref().unwrap().render("[", "]"), "deep [convolutional] networks");
+}
+
+#[test]
+fn pattern_search_check() {
+    let index_path: String = test_index_path("pattern_search_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+
+    let contents = PdfContents {
+        pdf_page_nums: vec![1, 2, 3],
+        pdf_texts: vec![
+            String::from("New Regulations for banks"),
+            String::from("a regulatory framework"),
+            String::from("the colour and the color"),
+        ],
+        ..PdfContents::default()
+    };
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("paper.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let wildcard_options = SearchOptions { mode: SearchMode::Wildcard, ..SearchOptions::default() };
+    let regex_options = SearchOptions { mode: SearchMode::Regex, ..SearchOptions::default() };
+    let cases = [
+        ("Regulat*", &wildcard_options, vec![1, 2]),
+        ("colo?r", &wildcard_options, vec![3]),
+        ("regulat*ry banks", &wildcard_options, vec![1, 2]),
+        ("regulat(ion|ory)", &regex_options, vec![2]),
+        ("colou?r", &regex_options, vec![3]),
+    ];
+    for (query, search_options, expected_pages) in cases {
+        let mut page_nums: Vec<u32> = pdf_seekers::search_operations::search_keyword_with_options(&index, query, search_options).unwrap()
+            .page_hits
+            .iter()
+            .map(|s| s.page_num)
+            .collect();
+        page_nums.sort();
+        assert_eq!(page_nums, expected_pages, "`{}` should match pages {:?}", query, expected_pages);
+    }
+
+    let result = pdf_seekers::search_operations::search_keyword_with_options(&index, "regulat(ion", &regex_options);
+    assert!(matches!(result, Err(SearchingError::InvalidSearchPattern(_, _))), "Invalid regular expressions should be rejected");
+
+    // Query syntax would otherwise be matched as literal patterns
+    for (query, search_options) in [("regulat* AND banks", &wildcard_options), ("-colo?r", &wildcard_options), ("title:regulat*", &wildcard_options), ("(colo?r)", &wildcard_options), ("colou?r OR banks", &regex_options), ("path:colou?r", &regex_options)] {
+        let result = pdf_seekers::search_operations::search_keyword_with_options(&index, query, search_options);
+        assert!(matches!(result, Err(SearchingError::InvalidSearchOptions(_))), "`{}` should be rejected", query);
+    }
+
+    // Snippets highlight the words matching the pattern
+    let page_hits = pdf_seekers::search_operations::search_keyword_with_options(&index, "Regulat*", &wildcard_options).unwrap().page_hits;
+    let page_hit = page_hits.iter().find(|s| s.page_num == 1).unwrap();
+    assert_eq!(page_hit.occurrences[0].term, "Regulations");
+    assert_eq!(page_hit.snippet.as_ref().unwrap().render("[", "]"), "New [Regulations] for banks");
+}
+
+#[test]
+fn snippet_extraction_check() {
+    let mut text_analyzer = tantivy::tokenizer::TokenizerManager::default().get("default").unwrap();
+    let search_options = SearchOptions::default();
+    let text: &str = "Région proposal Networks, (networks) and sub-networks; NETWORKS.";
+
+    // Every occurrence is found, whatever its case and the punctuation attached to it
+    let occurrences = pdf_seekers::search_operations::find_keyword_occurrences(text, 4, "networks", &search_options, &mut text_analyzer);
+    assert_eq!(occurrences.iter().map(|s| s.term.as_str()).collect::<Vec<&str>>(), vec!["Networks", "networks", "networks", "NETWORKS"]);
+    for occurrence in &occurrences {
+        assert_eq!(occurrence.page_num, 4);
+        assert_eq!(text.chars().skip(occurrence.start).take(occurrence.end - occurrence.start).collect::<String>(), occurrence.term);
+    }
+    assert_eq!((occurrences[0].start, occurrences[0].end), (16, 24));
+
+    // Keywords that are part of a larger word, absent or made of query syntax never panic
+    let occurrences = pdf_seekers::search_operations::find_keyword_occurrences(text, 4, "sub", &search_options, &mut text_analyzer);
+    assert_eq!(occurrences.len(), 1);
+    for keyword in ["missing", "", "AND", "(networks", "language:german"] {
+        let _ = pdf_seekers::search_operations::find_keyword_occurrences(text, 4, keyword, &search_options, &mut text_analyzer);
+    }
+
+    // Snippets are generated from the stored page contents, e.g. for `CNN` within `R-CNN`, without reading the PDF file again
+    let index_path: String = test_index_path("snippet_extraction_check");
+    let index = pdf_seekers::index_operations::create_or_open_index(&index_path).unwrap();
+    let contents = PdfContents::from(pdf_seekers::file_operations::read_pdf(&String::from("data/fast_rcnn.pdf"), false, None).unwrap());
+    let (num_pages, document_info) = (contents.pdf_page_nums.len(), contents.document_info.clone());
+    let fingerprint = FileFingerprint { size: 1, modified: 1, content_hash: String::from("a") };
+    let mut index_writer = pdf_seekers::index_operations::create_index_writer(&index, &IndexingOptions::default()).unwrap();
+    pdf_seekers::index_operations::parse_and_index_pdf("moved/fast_rcnn.pdf", &contents, &fingerprint, &mut index_writer).unwrap();
+
+    let page_hits = pdf_seekers::search_operations::search_keyword(&index, "CNN").unwrap();
+    let metadata = pdf_seekers::search_operations::run_analysis("moved/fast_rcnn.pdf", &page_hits);
+    assert_eq!(metadata.num_pages, num_pages);
+    assert_eq!(metadata.title, document_info.title);
+    assert!(metadata.matched_page_nums.contains(&1));
+    assert!(metadata.occurrences.iter().filter(|s| s.page_num == 1).count() > 1, "All occurrences on a page should be returned");
+    assert_eq!(metadata.snippets.len(), metadata.matched_page_nums.len());
+    for snippet in &metadata.snippets {
+        assert!(!snippet.highlighted.is_empty());
+        for (start, end) in &snippet.highlighted {
+            assert_eq!(snippet.fragment.chars().skip(*start).take(end - start).collect::<String>().to_lowercase(), "cnn");
+        }
+    }
+}